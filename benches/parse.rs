@@ -0,0 +1,53 @@
+//! Rough parsing throughput, run with `cargo bench`.
+//!
+//! No benchmarking harness is pulled in: each case is timed over a fixed
+//! number of rounds with [`Instant`], which is enough to compare revisions.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use squall_dot_io_resp::{Scratch, Value};
+
+fn bench(name: &str, source: &str, rounds: u32) {
+    let start = Instant::now();
+
+    for _ in 0..rounds {
+        black_box(Value::try_from(black_box(source)).unwrap());
+    }
+
+    println!("{name:<32} {:>12.2?}/iter", start.elapsed() / rounds);
+
+    let start = Instant::now();
+
+    for _ in 0..rounds {
+        Value::validate(black_box(source)).unwrap();
+    }
+
+    let validate = format!("{name} (validate)");
+
+    println!("{validate:<32} {:>12.2?}/iter", start.elapsed() / rounds);
+
+    let mut scratch = Scratch::new();
+    let start = Instant::now();
+
+    for _ in 0..rounds {
+        let value = Value::parse_in(black_box(source), &mut scratch).unwrap();
+        scratch.recycle(black_box(value));
+    }
+
+    let parse_in = format!("{name} (parse_in)");
+
+    println!("{parse_in:<32} {:>12.2?}/iter", start.elapsed() / rounds);
+}
+
+fn main() {
+    let long_simple_string = format!("+{}\r\n", "a".repeat(1 << 20));
+    let long_error = format!("-{}\r\n", "e".repeat(1 << 20));
+    let many_integers = format!("*{}\r\n{}", 1 << 16, ":123456789\r\n".repeat(1 << 16));
+    let many_bulk_strings = format!("*{}\r\n{}", 1 << 16, "$5\r\nHello\r\n".repeat(1 << 16));
+
+    bench("long simple string", &long_simple_string, 200);
+    bench("long error", &long_error, 200);
+    bench("many integers", &many_integers, 50);
+    bench("many bulk strings", &many_bulk_strings, 50);
+}
@@ -0,0 +1,198 @@
+//! Frame RESP values in [Tokio](https://tokio.rs) byte streams, e.g. to
+//! read and write them over a [`tokio_util::codec::Framed`] socket.
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::error::Error;
+use crate::options::EncodeOptions;
+use crate::value::Value;
+
+/// A [`Decoder`] and [`Encoder`] of RESP values.
+///
+/// Each call decodes exactly one [`Value`], leaving the bytes past it in the
+/// buffer. Should the buffer end before that value does, nothing is decoded
+/// yet: [`Error::Incomplete`] errors are told apart from malformed RESP,
+/// which is reported as is.
+///
+/// Values are encoded as [`Value::to_resp_bytes`] serializes them.
+///
+/// ```rust
+/// use bytes::BytesMut;
+/// use tokio_util::codec::Decoder;
+/// use squall_dot_io_resp::{RespCodec, Value};
+///
+/// let mut codec = RespCodec::new();
+/// let mut buffer = BytesMut::from(&b":1\r\n:2"[..]);
+///
+/// assert_eq!(codec.decode(&mut buffer), Ok(Some(Value::Integer(1))));
+/// assert_eq!(codec.decode(&mut buffer), Ok(None));
+/// assert_eq!(&buffer[..], b":2");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RespCodec;
+
+impl RespCodec {
+    /// Create a codec.
+    pub fn new() -> RespCodec {
+        RespCodec
+    }
+}
+
+impl Decoder for RespCodec {
+    type Item = Value;
+    type Error = Error;
+
+    fn decode(&mut self, buffer: &mut BytesMut) -> Result<Option<Value>, Error> {
+        match Value::parse_prefix_bytes(buffer) {
+            Ok((value, size)) => {
+                buffer.advance(size);
+                Ok(Some(value))
+            }
+            Err(error) if error.is_recoverable() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Bytes left once the stream ended are reported as the
+    /// [`Error::Incomplete`] error of the value they start.
+    fn decode_eof(&mut self, buffer: &mut BytesMut) -> Result<Option<Value>, Error> {
+        match Value::parse_prefix_bytes(buffer) {
+            Ok((value, size)) => {
+                buffer.advance(size);
+                Ok(Some(value))
+            }
+            Err(_) if buffer.is_empty() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl Encoder<Value> for RespCodec {
+    type Error = Error;
+
+    fn encode(&mut self, value: Value, buffer: &mut BytesMut) -> Result<(), Error> {
+        buffer.reserve(value.encoded_len());
+
+        // Writing into a `BytesMut` can't fail: it grows as needed.
+        let _ = value.write_resp(buffer, &EncodeOptions::default());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    use crate::Node::{ARRAY, BULK_STRING, INTEGER};
+    use crate::{Error, RespCodec, Value};
+
+    #[test]
+    fn resp_codec_decode_fragmented_chunks() {
+        let source = b"*2\r\n$5\r\nHello\r\n:-1\r\n+OK\r\n";
+        let mut codec = RespCodec::new();
+        let mut buffer = BytesMut::new();
+        let mut values = vec![];
+
+        for chunk in source.chunks(3) {
+            buffer.extend_from_slice(chunk);
+
+            while let Some(value) = codec.decode(&mut buffer).unwrap() {
+                values.push(value);
+            }
+        }
+
+        assert_eq!(
+            values,
+            [
+                Value::Array(vec![Value::String("Hello".into()), Value::Integer(-1)]),
+                Value::SimpleString("OK".into())
+            ]
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn resp_codec_decode_byte_by_byte() {
+        let source = "$7\r\nHéllo!\r\n".as_bytes();
+        let mut codec = RespCodec::new();
+        let mut buffer = BytesMut::new();
+
+        for byte in &source[..source.len() - 1] {
+            buffer.extend_from_slice(&[*byte]);
+            assert_eq!(codec.decode(&mut buffer), Ok(None));
+        }
+
+        buffer.extend_from_slice(b"\n:1");
+        assert_eq!(
+            codec.decode(&mut buffer),
+            Ok(Some(Value::String("Héllo!".into())))
+        );
+        assert_eq!(&buffer[..], b":1");
+    }
+
+    #[test]
+    fn resp_codec_decode_malformed() {
+        let mut codec = RespCodec::new();
+
+        assert_eq!(
+            codec.decode(&mut BytesMut::from(&b":1x\r\n"[..])),
+            Err(Error::of_type(INTEGER, 1))
+        );
+        assert_eq!(
+            codec.decode(&mut BytesMut::from(&b"$2\r\nHello\r\n"[..])),
+            Err(Error::of_size(BULK_STRING, 6))
+        );
+    }
+
+    #[test]
+    fn resp_codec_decode_eof() {
+        let mut codec = RespCodec::new();
+        let mut buffer = BytesMut::from(&b":1\r\n*2\r\n:2\r\n"[..]);
+
+        assert_eq!(codec.decode_eof(&mut buffer), Ok(Some(Value::Integer(1))));
+        assert_eq!(
+            codec.decode_eof(&mut buffer),
+            Err(Error::of_incomplete(ARRAY, 8))
+        );
+        assert_eq!(codec.decode_eof(&mut BytesMut::new()), Ok(None));
+    }
+
+    #[test]
+    fn resp_codec_encode() {
+        let value = Value::Array(vec![Value::Nil, Value::String("Hello".into())]);
+        let mut codec = RespCodec::new();
+        let mut buffer = BytesMut::from(&b"+OK\r\n"[..]);
+
+        codec.encode(value.clone(), &mut buffer).unwrap();
+        assert_eq!(
+            &buffer[..],
+            [&b"+OK\r\n"[..], &value.to_resp_bytes()].concat()
+        );
+    }
+
+    #[test]
+    fn resp_codec_round_trip() {
+        let values = [
+            Value::Nil,
+            Value::Integer(-447),
+            Value::Error("ERR oops".into()),
+            Value::String("Si vis pacem,\r\npara bellum".into()),
+            Value::SimpleString("Hourly".into()),
+            Value::Array(vec![Value::Integer(1), Value::Array(vec![])]),
+            Value::Map(vec![(Value::SimpleString("a".into()), Value::Nil)]),
+        ];
+        let mut codec = RespCodec::new();
+        let mut buffer = BytesMut::new();
+
+        for value in values.clone() {
+            codec.encode(value, &mut buffer).unwrap();
+        }
+        for value in values {
+            assert_eq!(codec.decode(&mut buffer), Ok(Some(value)));
+        }
+        assert_eq!(codec.decode(&mut buffer), Ok(None));
+        assert!(buffer.is_empty());
+    }
+}
@@ -0,0 +1,445 @@
+//! Deserialize [`Value`]s with [serde](https://serde.rs).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use serde::de::value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{
+    Deserialize, DeserializeOwned, Deserializer, EnumAccess, Error, IntoDeserializer, MapAccess,
+    SeqAccess, Unexpected, VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+use crate::error::Error as TError;
+use crate::value::Value;
+
+/// Deserialize values out of the serde data model, as they are serialized:
+/// nones and units as [`Value::Nil`], integers within the [`i64`] range as
+/// [`Value::Integer`]s, strings as bulk [`Value::String`]s, sequences as
+/// arrays and maps as maps.
+///
+/// The `Error` variant of a `Value` enum is read as a [`Value::Error`]: in
+/// JSON, this is any object with a sole `"Error"` key to a string. Floats and
+/// booleans, which have no RESP2 analog, are rejected.
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// assert_eq!(
+///     serde_json::from_str::<Value>(r#"[null,447,"Hourly",{"Error":"ERR oops"}]"#).unwrap(),
+///     Value::Array(vec![
+///         Value::Nil,
+///         Value::Integer(447),
+///         Value::String("Hourly".into()),
+///         Value::Error("ERR oops".into()),
+///     ])
+/// );
+/// ```
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Build a [`Value`] out of whichever serde type is found.
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+        f.write_str("a RESP value")
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_i64<E: Error>(self, integer: i64) -> Result<Value, E> {
+        Ok(Value::Integer(integer))
+    }
+
+    fn visit_u64<E: Error>(self, integer: u64) -> Result<Value, E> {
+        i64::try_from(integer)
+            .map(Value::Integer)
+            .map_err(|_| E::invalid_value(Unexpected::Unsigned(integer), &self))
+    }
+
+    fn visit_str<E: Error>(self, string: &str) -> Result<Value, E> {
+        Ok(Value::String(string.into()))
+    }
+
+    fn visit_string<E: Error>(self, string: String) -> Result<Value, E> {
+        Ok(Value::String(string))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default().min(4096));
+
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::Array(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or_default().min(4096));
+
+        while let Some(pair) = map.next_entry()? {
+            pairs.push(pair);
+        }
+
+        match <[_; 1]>::try_from(pairs) {
+            Ok([(Value::String(key), Value::String(message))]) if "Error" == key => {
+                Ok(Value::Error(message))
+            }
+            Ok(pairs) => Ok(Value::Map(pairs.into())),
+            Err(pairs) => Ok(Value::Map(pairs)),
+        }
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Value, A::Error> {
+        let (variant, access): (String, _) = data.variant()?;
+
+        match variant.as_str() {
+            "Error" => access.newtype_variant().map(Value::Error),
+            _ => Err(A::Error::unknown_variant(&variant, &["Error"])),
+        }
+    }
+}
+
+impl Value {
+    /// Deserialize this value into any `T`, e.g. the reply of an RPC-style
+    /// command into its own struct.
+    ///
+    /// [`Value::Nil`] is read as a unit or a none, arrays as sequences, tuples
+    /// or structs, and maps as maps or structs. Besides, Redis conventions are
+    /// followed for types RESP2 has no analog for: booleans are read from the
+    /// `0` and `1` integers, and floats from strings.
+    ///
+    /// A value which doesn't fit `T` is reported as an [`TError::Serde`]
+    /// error.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*2\r\n+Hourly\r\n:447\r\n").unwrap();
+    ///
+    /// assert_eq!(
+    ///     value.deserialize_into::<(String, u16)>(),
+    ///     Ok(("Hourly".into(), 447))
+    /// );
+    /// ```
+    pub fn deserialize_into<T: DeserializeOwned>(self) -> Result<T, TError> {
+        T::deserialize(self)
+    }
+}
+
+impl Error for TError {
+    fn custom<T: Display>(message: T) -> TError {
+        <TError as serde::ser::Error>::custom(message)
+    }
+}
+
+impl<'de> IntoDeserializer<'de, TError> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Value {
+        self
+    }
+}
+
+/// Feed a value to serde visitors, as [`Value::deserialize_into`] does.
+impl<'de> Deserializer<'de> for Value {
+    type Error = TError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TError> {
+        match self {
+            Value::Nil => visitor.visit_unit(),
+            Value::Integer(integer) => visitor.visit_i64(integer),
+            Value::Error(message) => {
+                visitor.visit_map(MapDeserializer::new(core::iter::once(("Error", message))))
+            }
+            Value::String(string) | Value::SimpleString(string) => visitor.visit_string(string),
+            Value::Array(values) => {
+                let mut seq = SeqDeserializer::new(values.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Map(pairs) => {
+                let mut map = MapDeserializer::new(pairs.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TError> {
+        match self {
+            Value::Integer(0) => visitor.visit_bool(false),
+            Value::Integer(1) => visitor.visit_bool(true),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TError> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TError> {
+        match &self {
+            Value::String(string) | Value::SimpleString(string) => match string.parse() {
+                Ok(float) => visitor.visit_f64(float),
+                Err(_) => Err(TError::invalid_value(Unexpected::Str(string), &visitor)),
+            },
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TError> {
+        match self {
+            Value::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, TError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants are read from strings, and other variants from maps of
+    /// a sole entry, keyed by the variant name.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TError> {
+        match self {
+            Value::String(string) | Value::SimpleString(string) => {
+                visitor.visit_enum(string.into_deserializer())
+            }
+            Value::Map(pairs) if 1 == pairs.len() => visitor.visit_enum(
+                MapAccessDeserializer::new(MapDeserializer::new(pairs.into_iter())),
+            ),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::{Error, Value};
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Point(i32, i32, Option<String>);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Role {
+        Master,
+        Replica { offset: u64 },
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Server {
+        name: String,
+        port: u16,
+        cluster: bool,
+        load: f64,
+        roles: Vec<Role>,
+        tags: Option<Vec<String>>,
+    }
+
+    #[test]
+    fn value_deserialize_from_json() {
+        for (json, value) in [
+            ("null", Value::Nil),
+            ("-447", Value::Integer(-447)),
+            ("9223372036854775807", Value::Integer(i64::MAX)),
+            (r#"{"Error":"ERR oops"}"#, Value::Error("ERR oops".into())),
+            (r#""Oh\r\nOh!""#, Value::String("Oh\r\nOh!".into())),
+            ("[]", Value::Array(vec![])),
+            (
+                "[1,[null]]",
+                Value::Array(vec![Value::Integer(1), Value::Array(vec![Value::Nil])]),
+            ),
+            (
+                r#"{"a":1,"b":null}"#,
+                Value::Map(vec![
+                    (Value::String("a".into()), Value::Integer(1)),
+                    (Value::String("b".into()), Value::Nil),
+                ]),
+            ),
+            (
+                r#"{"Error":1}"#,
+                Value::Map(vec![(Value::String("Error".into()), Value::Integer(1))]),
+            ),
+            ("{}", Value::Map(vec![])),
+        ] {
+            assert_eq!(
+                serde_json::from_str::<Value>(json).unwrap(),
+                value,
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn value_deserialize_reject_values_without_analog() {
+        for json in ["9223372036854775808", "4.47", "true"] {
+            assert!(serde_json::from_str::<Value>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn value_serde_round_trip() {
+        let value = Value::Array(vec![
+            Value::Nil,
+            Value::Integer(i64::MIN),
+            Value::Error("ERR oops".into()),
+            Value::String("Si vis pacem,\r\npara bellum".into()),
+            Value::Map(vec![(
+                Value::String("nested".into()),
+                Value::Array(vec![Value::Map(vec![])]),
+            )]),
+        ]);
+        let json = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn value_deserialize_into_tuple_struct() {
+        let value = Value::try_from("*3\r\n:-1\r\n:447\r\n$5\r\nHello\r\n").unwrap();
+
+        assert_eq!(
+            value.deserialize_into::<Point>(),
+            Ok(Point(-1, 447, Some("Hello".into())))
+        );
+        assert_eq!(
+            Value::try_from("*3\r\n:1\r\n:2\r\n$-1\r\n")
+                .unwrap()
+                .deserialize_into::<Point>(),
+            Ok(Point(1, 2, None))
+        );
+    }
+
+    #[test]
+    fn value_deserialize_into_struct() {
+        let value = Value::try_from(concat!(
+            "%6\r\n",
+            "+name\r\n$6\r\nHourly\r\n",
+            "+port\r\n:6379\r\n",
+            "+cluster\r\n:1\r\n",
+            "+load\r\n$4\r\n0.75\r\n",
+            "+roles\r\n*2\r\n+master\r\n%1\r\n+replica\r\n%1\r\n+offset\r\n:447\r\n",
+            "+tags\r\n$-1\r\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            value.deserialize_into::<Server>(),
+            Ok(Server {
+                name: "Hourly".into(),
+                port: 6379,
+                cluster: true,
+                load: 0.75,
+                roles: vec![Role::Master, Role::Replica { offset: 447 }],
+                tags: None,
+            })
+        );
+    }
+
+    #[test]
+    fn value_deserialize_into_value() {
+        let value = Value::Array(vec![
+            Value::Nil,
+            Value::Error("ERR oops".into()),
+            Value::SimpleString("OK".into()),
+            Value::Map(vec![(Value::Integer(1), Value::Array(vec![]))]),
+        ]);
+
+        assert_eq!(
+            value.clone().deserialize_into::<Value>(),
+            Ok(Value::Array(vec![
+                Value::Nil,
+                Value::Error("ERR oops".into()),
+                Value::String("OK".into()),
+                Value::Map(vec![(Value::Integer(1), Value::Array(vec![]))]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn value_deserialize_into_report_mismatches() {
+        for (value, message) in [
+            (
+                Value::Array(vec![Value::Integer(1)]),
+                "invalid length 1, expected tuple struct Point with 3 elements",
+            ),
+            (
+                Value::Array(vec![
+                    Value::Integer(1),
+                    Value::Integer(2),
+                    Value::Nil,
+                    Value::Nil,
+                ]),
+                "invalid length 4, expected 3 elements in sequence",
+            ),
+            (
+                Value::String("447".into()),
+                "invalid type: string \"447\", expected tuple struct Point",
+            ),
+            (
+                Value::Array(vec![Value::Integer(i64::MAX); 3]),
+                "invalid value: integer `9223372036854775807`, expected i32",
+            ),
+        ] {
+            assert_eq!(
+                value.deserialize_into::<Point>(),
+                Err(Error::Serde {
+                    message: message.into()
+                })
+            );
+        }
+        assert_eq!(
+            Value::Integer(2)
+                .deserialize_into::<bool>()
+                .unwrap_err()
+                .to_string(),
+            "invalid type: integer `2`, expected a boolean"
+        );
+        assert_eq!(
+            Value::String("NaN?".into())
+                .deserialize_into::<f64>()
+                .unwrap_err()
+                .to_string(),
+            "invalid value: string \"NaN?\", expected f64"
+        );
+    }
+}
@@ -1,22 +1,20 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Error {
     /// Denote a size mismatch data in the RESP string.
     ///
-    /// This means that observed [`super::Value::String`] length or
-    /// [`super::Value::Array`] size is fewer than the expected size.
-    ///
-    /// + For bulk [`super::Value::String`], this can be checked by looking
-    ///   at `\r\n` sequence at the end of the byte sequence;
-    /// + For bulk [`super::Value::Array`], this means we parsed all the string
-    ///   but the entries count of the array is fewer than expected.
+    /// This means that observed bulk [`super::Value::String`] length differs
+    /// from the expected size, which is checked by looking at the `\r\n`
+    /// sequence at the end of the byte sequence. _(A RESP string ending
+    /// before the expected size is reached is rather an [`Error::Incomplete`]
+    /// one.)_
     ///
-    /// The `index` indicates:
-    /// + For bulk [`super::Value::String`], that `\r\n` sequence was expected
-    ///   at that index _(which is right after the specified bulk string size.
-    ///   )_
-    /// + For [`super::Value::Array`], that some byte were still expected at
-    ///   that position.
+    /// The `index` indicates that `\r\n` sequence was expected at that index
+    /// _(which is right after the specified bulk string size.)_
     ///
     /// The `node` indicates which token was being processed when the type
     /// mismatch was noticed.
@@ -26,26 +24,80 @@ pub enum Error {
     ///
     /// The `index` indicates at which byte it happened. _(So far, the only
     /// RESP token that need type conversion is [`super::Value::Integer`].
-    /// So this error will mean that the integer/size value was not parsable
-    /// into [`i64`].)_
+    /// So this error will mean that the integer/size value was not a number.
+    /// Numbers out of the [`i64`] range are rather [`Error::Overflow`]
+    /// errors.)_
     ///
     /// The `node` indicates which token was being processed when the type
     /// mismatch was noticed.
     Type { index: usize, node: Node },
 
+    /// Denote an integer/size value out of the [`i64`] range.
+    ///
+    /// Although well-formed, such a value cannot be represented by
+    /// [`super::Value::Integer`]. _(This is told apart from [`Error::Type`],
+    /// which denotes values that aren't numbers at all.)_ Arrays and maps
+    /// sizes beyond [`super::ParseOptions::max_array_len`] are reported as
+    /// such as well, and so are aggregates nested deeper than
+    /// [`super::ParseOptions::max_depth`], at the index of their header.
+    ///
+    /// The `index` indicates at which byte the value starts.
+    ///
+    /// The `node` indicates which token was being processed when the
+    /// overflow happened.
+    Overflow { index: usize, node: Node },
+
     /// Denote an unexpected byte in the RESP string.
     ///
-    /// The `index` indicate at which byte it happened. _(Should its value be
-    /// larger than the parser string, it will indicate that some bytes where
-    /// expected there: usually, the `\r\n` sequence.)_
+    /// The `index` indicate at which byte it happened. _(A RESP string ending
+    /// where more bytes were expected is rather an [`Error::Incomplete`]
+    /// one.)_
+    ///
+    /// The `byte` is the offending one, found at that index.
     ///
     /// The `node` indicates which token was being processed when the
     /// unexpected happened.
-    Unexpected { index: usize, node: Node },
+    Unexpected { index: usize, node: Node, byte: u8 },
+
+    /// Denote a RESP string which ended before the value was complete.
+    ///
+    /// Unlike the other variants, the bytes read so far are not malformed:
+    /// the same RESP string followed by the missing bytes may well be valid.
+    /// This is what stream readers check to know that they should wait for
+    /// more bytes: e.g. the `\r\n` sequence of an integer, the payload of a
+    /// bulk [`super::Value::String`] or the entries of an
+    /// [`super::Value::Array`].
+    ///
+    /// The `index` indicates where more bytes were expected, which is the
+    /// length of the parsed string. _(In particular, arrays and maps with
+    /// fewer entries than announced aren't [`Error::Size`] errors: they
+    /// always report this index, with their own node, however deeply nested
+    /// they are, as long as their last read entry is complete.)_
+    ///
+    /// The `node` indicates which token was being processed when the end of
+    /// the RESP string was reached.
+    Incomplete { index: usize, node: Node },
+
+    /// Denote a failure to read the RESP string, e.g. from a socket, rather
+    /// than a malformed RESP string.
+    ///
+    /// Only the `kind` of the IO error is kept, so that errors can still be
+    /// cloned and compared. Having no RESP string to point into, its index is
+    /// `0` and its node is [`Node::UNKNOWN`].
+    #[cfg(feature = "std")]
+    Io { kind: std::io::ErrorKind },
+
+    /// Denote a value which doesn't fit the type it is deserialized into, or
+    /// a type which can't be serialized into a value, with serde.
+    ///
+    /// Its `message` is the one serde reports. Having no RESP string to point
+    /// into, its index is `0` and its node is [`Node::UNKNOWN`].
+    #[cfg(feature = "serde")]
+    Serde { message: String },
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Node {
     SIMPLE_STRING,
     BULK_STRING,
@@ -55,25 +107,220 @@ pub enum Node {
     ARRAY,
     SIZE,
     NIL,
+    MAP,
+    ATTRIBUTE,
+}
+
+impl Node {
+    /// Whether this node holds nested values: [`Node::ARRAY`], [`Node::MAP`]
+    /// and [`Node::ATTRIBUTE`].
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert!(Value::try_from("*2\r\n:1\r\n").unwrap_err().node().is_aggregate());
+    /// ```
+    pub fn is_aggregate(&self) -> bool {
+        matches!(self, Node::ARRAY | Node::MAP | Node::ATTRIBUTE)
+    }
+
+    /// Whether this node holds a string: [`Node::SIMPLE_STRING`],
+    /// [`Node::BULK_STRING`], and [`Node::ERROR`], which is a simple string
+    /// on the wire.
+    pub fn is_string_like(&self) -> bool {
+        matches!(self, Node::SIMPLE_STRING | Node::BULK_STRING | Node::ERROR)
+    }
 }
 
 impl Error {
     pub fn index(&self) -> &usize {
         match &self {
             Self::Type { index, .. } => index,
+            Self::Overflow { index, .. } => index,
             Self::Size { index, .. } => index,
             Self::Unexpected { index, .. } => index,
+            Self::Incomplete { index, .. } => index,
+            #[cfg(feature = "std")]
+            Self::Io { .. } => &0,
+            #[cfg(feature = "serde")]
+            Self::Serde { .. } => &0,
         }
     }
 
+    /// Move the index of this error `delta` bytes further, for errors
+    /// reported on a sub-slice of a larger RESP string: the index then points
+    /// into that larger string. The node, and the offending byte of
+    /// [`Error::Unexpected`] errors, are kept.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::{Error, Node, Value};
+    ///
+    /// let buffer = "+OK\r\n:X\r\n";
+    /// let error = Value::try_from(&buffer[5..]).unwrap_err();
+    ///
+    /// assert_eq!(error.with_offset(5), Error::of_type(Node::INTEGER, 6));
+    /// ```
+    pub fn with_offset(mut self, delta: usize) -> Error {
+        match &mut self {
+            Self::Type { index, .. }
+            | Self::Overflow { index, .. }
+            | Self::Size { index, .. }
+            | Self::Unexpected { index, .. }
+            | Self::Incomplete { index, .. } => *index += delta,
+            #[cfg(feature = "std")]
+            Self::Io { .. } => {}
+            #[cfg(feature = "serde")]
+            Self::Serde { .. } => {}
+        }
+        self
+    }
+
     pub fn node(&self) -> &Node {
         match &self {
             Self::Type { node, .. } => node,
+            Self::Overflow { node, .. } => node,
             Self::Size { node, .. } => node,
             Self::Unexpected { node, .. } => node,
+            Self::Incomplete { node, .. } => node,
+            #[cfg(feature = "std")]
+            Self::Io { .. } => &Node::UNKNOWN,
+            #[cfg(feature = "serde")]
+            Self::Serde { .. } => &Node::UNKNOWN,
         }
     }
 
+    /// Whether parsing may succeed once more bytes are read, which is only
+    /// the case of [`Error::Incomplete`] errors.
+    ///
+    /// Other errors denote malformed RESP strings: reading more bytes won't
+    /// help.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert!(Value::try_from(":1").unwrap_err().is_recoverable());
+    /// assert!(!Value::try_from(":X\r\n").unwrap_err().is_recoverable());
+    /// ```
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, Self::Incomplete { .. })
+    }
+
+    /// Convert into an IO error wrapping this one, for use in
+    /// [`std::io::Read`] adapters: [`Error::Incomplete`] errors are
+    /// [`std::io::ErrorKind::UnexpectedEof`] ones, [`Error::Io`] ones keep
+    /// their kind, and other errors are [`std::io::ErrorKind::InvalidData`]
+    /// ones.
+    ///
+    /// ```rust
+    /// use std::io::ErrorKind;
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let error = Value::try_from(":1").unwrap_err().into_io_error();
+    ///
+    /// assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    /// assert_eq!(error.to_string(), "incomplete INTEGER, more bytes expected at index 2");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn into_io_error(self) -> std::io::Error {
+        let kind = match self {
+            Self::Incomplete { .. } => std::io::ErrorKind::UnexpectedEof,
+            Self::Io { kind } => kind,
+            _ => std::io::ErrorKind::InvalidData,
+        };
+
+        std::io::Error::new(kind, self)
+    }
+
+    /// Slice the text of the number which failed to parse out of the `source`
+    /// this error was reported for, `None` for errors other than
+    /// [`Error::Type`] and [`Error::Overflow`].
+    ///
+    /// That text spans from the error index to the end of the line, for
+    /// integers as well as for bulk string, array and map sizes.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let source = ":Yikes\r\n";
+    /// let error = Value::try_from(source).unwrap_err();
+    ///
+    /// assert_eq!(error.span(source), Some("Yikes"));
+    /// ```
+    pub fn span<'a>(&self, source: &'a str) -> Option<&'a str> {
+        match self {
+            Self::Type { index, .. } | Self::Overflow { index, .. } => {
+                let text = source.get(*index..)?;
+                let end = text.find(['\r', '\n']).unwrap_or(text.len());
+
+                Some(&text[..end])
+            }
+            _ => None,
+        }
+    }
+
+    /// Render this error against the `source` it was reported for, as a
+    /// compiler-style diagnostic pointing at its index with a `^` caret.
+    ///
+    /// The RESP line holding that index is shown with its `\r`, `\n` and
+    /// other control characters escaped, so that the caret lines up.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let source = "*2\r\n:1\r\n:2x\r\n";
+    /// let error = Value::try_from(source).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.render(source),
+    ///     [
+    ///         "invalid INTEGER at index 9",
+    ///         " --> line 3, column 2",
+    ///         "  |",
+    ///         "3 | :2x\\r\\n",
+    ///         "  |  ^",
+    ///     ]
+    ///     .join("\n")
+    /// );
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let index = *self.index();
+        let start = source.as_bytes()[..index.min(source.len())]
+            .iter()
+            .rposition(|byte| b'\n' == *byte)
+            .map_or(0, |i| i + 1);
+        let end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i + 1);
+        let number = source[..start].matches('\n').count() + 1;
+
+        let mut line = String::new();
+        let mut caret = None;
+
+        for (i, char) in source[start..end].char_indices() {
+            if caret.is_none() && index < start + i + char.len_utf8() {
+                caret = Some(line.chars().count());
+            }
+            match char {
+                '\r' => line.push_str("\\r"),
+                '\n' => line.push_str("\\n"),
+                '\t' => line.push_str("\\t"),
+                char if char.is_control() => line.push_str(&format!("\\x{:02x}", char as u32)),
+                char => line.push(char),
+            }
+        }
+
+        let caret = caret.unwrap_or_else(|| line.chars().count());
+        let gutter = " ".repeat(number.to_string().len());
+
+        format!(
+            "{self}\n{gutter}--> line {number}, column {}\n{gutter} |\n{number} | {line}\n{gutter} | {}^",
+            index - start + 1,
+            " ".repeat(caret)
+        )
+    }
+
     pub fn of_size(node: Node, index: usize) -> Error {
         Error::Size { index, node }
     }
@@ -82,7 +329,234 @@ impl Error {
         Error::Type { index, node }
     }
 
-    pub fn of_unexpected(node: Node, index: usize) -> Error {
-        Error::Unexpected { index, node }
+    pub fn of_overflow(node: Node, index: usize) -> Error {
+        Error::Overflow { index, node }
+    }
+
+    pub fn of_unexpected(node: Node, index: usize, byte: u8) -> Error {
+        Error::Unexpected { index, node, byte }
+    }
+
+    pub fn of_incomplete(node: Node, index: usize) -> Error {
+        Error::Incomplete { index, node }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Size { index, node } => write!(f, "size mismatch of {node:?} at index {index}"),
+            Self::Type { index, node } => write!(f, "invalid {node:?} at index {index}"),
+            Self::Overflow { index, node } => {
+                write!(f, "out of range {node:?} at index {index}")
+            }
+            Self::Unexpected { index, node, byte } => {
+                write!(
+                    f,
+                    "unexpected byte {byte:#04x} in {node:?} at index {index}"
+                )
+            }
+            Self::Incomplete { index, node } => {
+                write!(
+                    f,
+                    "incomplete {node:?}, more bytes expected at index {index}"
+                )
+            }
+            #[cfg(feature = "std")]
+            Self::Io { kind } => write!(f, "failed to read RESP: {kind}"),
+            #[cfg(feature = "serde")]
+            Self::Serde { message } => f.write_str(message),
+        }
+    }
+}
+
+/// This is [`std::error::Error`] with the `std` feature, which serde also
+/// requires of its errors.
+#[cfg(any(feature = "std", feature = "serde"))]
+impl core::error::Error for Error {}
+
+/// Same as [`Error::into_io_error`].
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        error.into_io_error()
+    }
+}
+
+/// Keep the kind of an IO error as an [`Error::Io`], for readers reporting
+/// both their own failures and malformed RESP.
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io { kind: error.kind() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Node::{
+        ARRAY, ATTRIBUTE, BULK_STRING, ERROR, INTEGER, MAP, NIL, SIMPLE_STRING, SIZE, UNKNOWN,
+    };
+    use crate::Value;
+
+    use super::Error;
+
+    #[test]
+    fn error_render_caret_under_index() {
+        assert_eq!(
+            Error::of_size(BULK_STRING, 9).render("$5\r\nOops\r\n"),
+            [
+                "size mismatch of BULK_STRING at index 9",
+                " --> line 2, column 6",
+                "  |",
+                "2 | Oops\\r\\n",
+                "  |       ^",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            Error::of_unexpected(UNKNOWN, 0, b'{').render("{}"),
+            [
+                "unexpected byte 0x7b in UNKNOWN at index 0",
+                " --> line 1, column 1",
+                "  |",
+                "1 | {}",
+                "  | ^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn error_render_caret_past_source_end() {
+        assert_eq!(
+            Error::of_incomplete(BULK_STRING, 7).render("$4\r\nOop"),
+            [
+                "incomplete BULK_STRING, more bytes expected at index 7",
+                " --> line 2, column 4",
+                "  |",
+                "2 | Oop",
+                "  |    ^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn error_is_recoverable_when_incomplete() {
+        assert!(Error::of_incomplete(ARRAY, 4).is_recoverable());
+        assert!(!Error::of_size(BULK_STRING, 4).is_recoverable());
+        assert!(!Error::of_type(INTEGER, 1).is_recoverable());
+        assert!(!Error::of_overflow(INTEGER, 1).is_recoverable());
+        assert!(!Error::of_unexpected(SIMPLE_STRING, 3, b'\n').is_recoverable());
+    }
+
+    #[test]
+    fn error_clone() {
+        let error = Error::of_incomplete(ARRAY, 4);
+        let errors = [error.clone(), error.clone()];
+
+        assert_eq!(errors, [Error::of_incomplete(ARRAY, 4), error]);
+    }
+
+    #[test]
+    fn error_span_of_numbers() {
+        for (source, span) in [
+            (":Yikes\r\n", "Yikes"),
+            (":5 \r\n", "5 "),
+            (":99999999999999999999\r\n", "99999999999999999999"),
+            ("$Yikes\r\nOops\r\n", "Yikes"),
+            ("*2\r\n:1\r\n*Yikes\r\n", "Yikes"),
+            ("%-\r\n", "-"),
+            (":1\n2\r\n", "1"),
+        ] {
+            let error = Value::try_from(source).unwrap_err();
+
+            assert_eq!(error.span(source), Some(span), "{source:?}");
+        }
+
+        assert_eq!(Error::of_incomplete(INTEGER, 2).span(":1"), None);
+        assert_eq!(Error::of_type(INTEGER, 9).span(":Yikes\r\n"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn error_into_io_error() {
+        use std::io::ErrorKind;
+
+        let incomplete = Error::of_incomplete(ARRAY, 4);
+        let size = Error::of_size(BULK_STRING, 9);
+
+        assert_eq!(
+            incomplete.clone().into_io_error().kind(),
+            ErrorKind::UnexpectedEof
+        );
+        assert_eq!(size.clone().into_io_error().kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            std::io::Error::from(Error::of_type(INTEGER, 1)).kind(),
+            ErrorKind::InvalidData
+        );
+
+        let error = size.clone().into_io_error();
+
+        assert_eq!(error.to_string(), size.to_string());
+        assert_eq!(
+            error.into_inner().unwrap().downcast_ref::<Error>(),
+            Some(&size)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn error_from_io_error() {
+        use std::io::ErrorKind;
+
+        let error = Error::from(std::io::Error::from(ErrorKind::ConnectionReset));
+
+        assert_eq!(
+            error,
+            Error::Io {
+                kind: ErrorKind::ConnectionReset
+            }
+        );
+        assert_eq!(error.index(), &0);
+        assert_eq!(error.node(), &UNKNOWN);
+        assert_eq!(error.clone().with_offset(3), error);
+        assert!(!error.is_recoverable());
+        assert_eq!(error.to_string(), "failed to read RESP: connection reset");
+        assert_eq!(error.into_io_error().kind(), ErrorKind::ConnectionReset);
+    }
+
+    #[test]
+    fn node_predicates() {
+        for node in [ARRAY, MAP, ATTRIBUTE] {
+            assert!(node.is_aggregate(), "{node:?}");
+            assert!(!node.is_string_like(), "{node:?}");
+        }
+        for node in [SIMPLE_STRING, BULK_STRING, ERROR] {
+            assert!(!node.is_aggregate(), "{node:?}");
+            assert!(node.is_string_like(), "{node:?}");
+        }
+        for node in [INTEGER, SIZE, NIL, UNKNOWN] {
+            assert!(!node.is_aggregate(), "{node:?}");
+            assert!(!node.is_string_like(), "{node:?}");
+        }
+    }
+
+    #[test]
+    fn error_with_offset() {
+        assert_eq!(
+            Error::of_size(BULK_STRING, 9).with_offset(3),
+            Error::of_size(BULK_STRING, 12)
+        );
+        assert_eq!(
+            Error::of_unexpected(MAP, 0, b'!').with_offset(7),
+            Error::of_unexpected(MAP, 7, b'!')
+        );
+        assert_eq!(
+            Error::of_incomplete(ARRAY, 4).with_offset(0),
+            Error::of_incomplete(ARRAY, 4)
+        );
+        assert_eq!(Error::of_overflow(SIZE, 1).with_offset(1).node(), &SIZE);
     }
 }
@@ -0,0 +1,127 @@
+//! Framing of RESP values read line by line, off readers which can't be
+//! peeked into.
+
+use alloc::vec::Vec;
+
+use crate::options::ParseOptions;
+
+/// What to read next.
+#[derive(Debug)]
+pub(crate) enum Next {
+    /// A line, up to its `\n`.
+    Line,
+    /// A payload of that many bytes, `\r\n` included.
+    Payload(u64),
+    /// Nothing: the bytes read so far are to be parsed.
+    Parse,
+}
+
+/// Count the values left to read in a RESP string read line by line, so that
+/// readers ([`crate::Value::from_reader`] and the like) only parse it once it is
+/// complete.
+///
+/// Lines aren't validated here: anything unexpected calls for parsing the
+/// bytes read so far, for the parser to report it.
+#[derive(Debug, Default)]
+pub(crate) struct Framer {
+    /// Entries count left to read in each open aggregate, `None` for RESP3
+    /// streamed arrays, and whether that aggregate is an attribute.
+    aggregates: Vec<(Option<usize>, bool)>,
+    /// Whether the chunks of a RESP3 streamed string are being read.
+    streaming: bool,
+}
+
+impl Framer {
+    pub(crate) fn on_line(&mut self, line: &[u8]) -> Next {
+        let Some((&kind, rest)) = line.strip_suffix(b"\r\n").and_then(<[u8]>::split_first) else {
+            return Next::Parse;
+        };
+        let len = core::str::from_utf8(rest)
+            .ok()
+            .and_then(|rest| rest.parse::<i64>().ok());
+
+        match (kind, len) {
+            (b';', Some(0)) if self.streaming => {
+                self.streaming = false;
+                self.on_value()
+            }
+            (b';', Some(size @ 1..)) if self.streaming => Next::Payload(size as u64 + 2),
+            _ if self.streaming => Next::Parse,
+            (b'*', _) if b"?" == rest => self.open(None, false),
+            (b'$', _) if b"?" == rest => {
+                self.streaming = true;
+                Next::Line
+            }
+            (b'.', _) => match self.aggregates.last() {
+                Some((None, _)) if rest.is_empty() => {
+                    self.aggregates.pop();
+                    self.on_value()
+                }
+                _ => Next::Parse,
+            },
+            (b'$', Some(-1)) | (b'*', Some(-1) | Some(0)) | (b'%', Some(0)) => self.on_value(),
+            (b'$', Some(size @ 0..)) => Next::Payload(size as u64 + 2),
+            (b'|', Some(0)) => Next::Line,
+            (b'*' | b'%' | b'|', Some(len @ 1..)) => {
+                let left = match kind {
+                    b'*' => usize::try_from(len).ok(),
+                    _ => usize::try_from(len).ok().and_then(|len| len.checked_mul(2)),
+                };
+
+                match left {
+                    Some(left) => self.open(Some(left), b'|' == kind),
+                    None => Next::Parse,
+                }
+            }
+            (b'$' | b'*' | b'%' | b'|', _) => Next::Parse,
+            _ => self.on_value(),
+        }
+    }
+
+    /// An aggregate header was read: past the default
+    /// [`ParseOptions::max_depth`], the parser is left to reject it.
+    fn open(&mut self, left: Option<usize>, attribute: bool) -> Next {
+        let depth = self.aggregates.len();
+
+        if ParseOptions::default()
+            .max_depth
+            .is_some_and(|max| max <= depth)
+        {
+            return Next::Parse;
+        }
+
+        self.aggregates.push((left, attribute));
+        Next::Line
+    }
+
+    /// The payload of a bulk string, or of a streamed string chunk, was read.
+    pub(crate) fn on_payload(&mut self) -> Next {
+        match self.streaming {
+            true => Next::Line,
+            false => self.on_value(),
+        }
+    }
+
+    /// A value was completed: close the aggregates it completes in turn.
+    pub(crate) fn on_value(&mut self) -> Next {
+        loop {
+            match self.aggregates.last_mut() {
+                None => return Next::Parse,
+                Some((Some(1), attribute)) => {
+                    let attribute = *attribute;
+
+                    self.aggregates.pop();
+                    // The annotated value is still to be read.
+                    if attribute {
+                        return Next::Line;
+                    }
+                }
+                Some((Some(left), _)) => {
+                    *left -= 1;
+                    return Next::Line;
+                }
+                Some((None, _)) => return Next::Line,
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Read RESP values off [`futures_io::AsyncRead`] readers, for async
+//! runtimes other than Tokio's codec framework.
+
+use alloc::vec;
+use core::future::poll_fn;
+use core::pin::Pin;
+
+use futures_io::AsyncRead;
+
+use crate::error::Error;
+use crate::framer::{Framer, Next};
+use crate::value::Value;
+
+/// Read a single RESP value from `reader`, as [`Value::from_reader`] does.
+///
+/// Lines are read one byte at a time, and bulk string payloads by chunks, so
+/// that nothing past that value is consumed: consider wrapping unbuffered
+/// readers into a buffered one. The bytes read are only parsed once the
+/// value is complete, or malformed, awaiting `reader` in between.
+///
+/// Malformed RESP is reported as is, `reader` running dry before the value
+/// is complete as the [`Error::Incomplete`] error of that value, and
+/// `reader` failures as [`Error::Io`] errors.
+///
+/// ```rust
+/// use futures_executor::block_on;
+/// use squall_dot_io_resp::futures::read_value;
+/// use squall_dot_io_resp::Value;
+///
+/// let mut reader = &b":1\r\n:2\r\n"[..];
+///
+/// assert_eq!(block_on(read_value(&mut reader)), Ok(Value::Integer(1)));
+/// assert_eq!(block_on(read_value(&mut reader)), Ok(Value::Integer(2)));
+/// ```
+pub async fn read_value<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Value, Error> {
+    let mut buffer = vec![];
+    let mut framer = Framer::default();
+    let mut next = Next::Line;
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        let start = buffer.len();
+
+        next = match next {
+            Next::Line => {
+                while !buffer[start..].ends_with(b"\n") {
+                    match read(reader, &mut chunk[..1]).await? {
+                        0 => return parse(&buffer),
+                        _ => buffer.push(chunk[0]),
+                    }
+                }
+                framer.on_line(&buffer[start..])
+            }
+            Next::Payload(size) => {
+                let mut left = size;
+
+                while 0 < left {
+                    let len = left.min(chunk.len() as u64) as usize;
+
+                    match read(reader, &mut chunk[..len]).await? {
+                        0 => return parse(&buffer),
+                        read => {
+                            buffer.extend_from_slice(&chunk[..read]);
+                            left -= read as u64;
+                        }
+                    }
+                }
+                match buffer.ends_with(b"\r\n") {
+                    true => framer.on_payload(),
+                    false => Next::Parse,
+                }
+            }
+            // Should the value be incomplete after all, it's parsed again
+            // after every line.
+            Next::Parse => match Value::parse_prefix_bytes(&buffer) {
+                Ok((value, _)) => return Ok(value),
+                Err(error) if error.is_recoverable() => Next::Line,
+                Err(error) => return Err(error),
+            },
+        };
+    }
+}
+
+/// Read some bytes off `reader` into `buffer`, `0` once it ran dry.
+async fn read<R: AsyncRead + Unpin>(reader: &mut R, buffer: &mut [u8]) -> Result<usize, Error> {
+    Ok(poll_fn(|cx| Pin::new(&mut *reader).poll_read(cx, buffer)).await?)
+}
+
+/// Parse what `reader` yielded before running dry.
+fn parse(buffer: &[u8]) -> Result<Value, Error> {
+    Value::parse_prefix_bytes(buffer).map(|(value, _)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures_executor::block_on;
+    use futures_io::AsyncRead;
+
+    use crate::Node::{BULK_STRING, UNKNOWN};
+
+    use super::super::{Error, Value};
+    use super::read_value;
+
+    /// Yield `chunks` one at a time, each poll but the first being pending.
+    struct Chunks<'a> {
+        chunks: Vec<&'a [u8]>,
+        pending: bool,
+    }
+
+    impl<'a> Chunks<'a> {
+        fn new(chunks: &[&'a [u8]]) -> Chunks<'a> {
+            Chunks {
+                chunks: chunks.iter().rev().copied().collect(),
+                pending: false,
+            }
+        }
+    }
+
+    impl AsyncRead for Chunks<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buffer: &mut [u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            let Some(chunk) = self.chunks.pop() else {
+                return Poll::Ready(Ok(0));
+            };
+            let len = chunk.len().min(buffer.len());
+
+            buffer[..len].copy_from_slice(&chunk[..len]);
+            if len < chunk.len() {
+                self.chunks.push(&chunk[len..]);
+            }
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    /// Fail every read.
+    struct Failing;
+
+    impl AsyncRead for Failing {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &mut [u8],
+        ) -> Poll<std::io::Result<usize>> {
+            Poll::Ready(Err(ErrorKind::ConnectionReset.into()))
+        }
+    }
+
+    #[test]
+    fn futures_read_value_await_chunks() {
+        let mut reader = Chunks::new(&[
+            b"*3\r\n$5",
+            b"\r\nHel",
+            b"lo\r\n:4",
+            b"2\r\n*?\r\n",
+            b"+OK\r\n.\r",
+            b"\n:7\r\n",
+        ]);
+
+        assert_eq!(
+            block_on(read_value(&mut reader)),
+            Ok(Value::Array(vec![
+                Value::String("Hello".into()),
+                Value::Integer(42),
+                Value::Array(vec![Value::SimpleString("OK".into())]),
+            ]))
+        );
+        assert_eq!(reader.chunks, [&b":7\r\n"[..]]);
+    }
+
+    #[test]
+    fn futures_read_value_one_at_a_time() {
+        let mut reader = &b"$2\r\nOK\r\n:1\r\n"[..];
+
+        assert_eq!(
+            block_on(read_value(&mut reader)),
+            Ok(Value::String("OK".into()))
+        );
+        assert_eq!(block_on(read_value(&mut reader)), Ok(Value::Integer(1)));
+    }
+
+    #[test]
+    fn futures_read_value_report_errors() {
+        assert_eq!(
+            block_on(read_value(&mut Chunks::new(&[b"$5\r\n", b"Oops"]))),
+            Err(Error::of_incomplete(BULK_STRING, 8))
+        );
+        assert_eq!(
+            block_on(read_value(&mut Chunks::new(&[]))),
+            Err(Error::of_incomplete(UNKNOWN, 0))
+        );
+        assert_eq!(
+            block_on(read_value(&mut Chunks::new(&[b"$2\r\nOops\r\n"]))),
+            Err(Error::of_size(BULK_STRING, 6))
+        );
+        assert_eq!(
+            block_on(read_value(&mut Failing)),
+            Err(Error::Io {
+                kind: ErrorKind::ConnectionReset
+            })
+        );
+    }
+}
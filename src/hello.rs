@@ -0,0 +1,50 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::value::Value;
+
+/// The server details replied to the `HELLO` handshake, as read by
+/// [`Value::as_hello`].
+///
+/// Fields missing from the reply are left out, and unknown ones are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HelloInfo {
+    /// The server name, e.g. `redis`.
+    pub server: Option<String>,
+    /// The server version, e.g. `7.2.4`.
+    pub version: Option<String>,
+    /// The protocol version negotiated, `2` or `3`.
+    pub proto: Option<i64>,
+    /// The connection id.
+    pub id: Option<i64>,
+    /// The server mode, e.g. `standalone` or `cluster`.
+    pub mode: Option<String>,
+    /// The server role, e.g. `master` or `replica`.
+    pub role: Option<String>,
+    /// The loaded modules, as replied: Redis describes each one with a map.
+    pub modules: Vec<Value>,
+}
+
+impl HelloInfo {
+    /// Gather the known fields found among `pairs`, keyed by strings.
+    pub(crate) fn from_pairs<'a>(pairs: impl Iterator<Item = (&'a Value, &'a Value)>) -> HelloInfo {
+        let mut hello = HelloInfo::default();
+
+        for (key, value) in pairs {
+            let string = value.as_str().map(ToString::to_string);
+
+            match key.as_str() {
+                Some("server") => hello.server = string,
+                Some("version") => hello.version = string,
+                Some("proto") => hello.proto = value.as_integer(),
+                Some("id") => hello.id = value.as_integer(),
+                Some("mode") => hello.mode = string,
+                Some("role") => hello.role = string,
+                Some("modules") => hello.modules = value.as_array().unwrap_or_default().to_vec(),
+                _ => {}
+            }
+        }
+
+        hello
+    }
+}
@@ -0,0 +1,208 @@
+//! Convert [`Value`]s from and into [`serde_json::Value`]s.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use serde_json::{Map, Value as Json};
+
+use crate::value::Value;
+
+/// Convert a value into JSON, as [`Value`]'s `Serialize` implementation
+/// does, when the `serde` feature is enabled: simple and bulk strings alike
+/// are JSON strings, and [`Value::Nil`] is `null`.
+///
+/// Errors have no JSON analog: they are objects with a sole `"Error"` key to
+/// their message. Map keys other than strings are written as their JSON
+/// text, e.g. `"1"` for [`Value::Integer`] `1`. Note that JSON objects keep
+/// a single value per key, and sort their keys unless `serde_json` has its
+/// `preserve_order` feature enabled.
+///
+/// ```rust
+/// use serde_json::json;
+/// use squall_dot_io_resp::Value;
+///
+/// let value = Value::try_from("*3\r\n$-1\r\n:447\r\n-ERR oops\r\n").unwrap();
+///
+/// assert_eq!(
+///     serde_json::Value::from(value),
+///     json!([null, 447, {"Error": "ERR oops"}])
+/// );
+/// ```
+impl From<Value> for Json {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Nil => Json::Null,
+            Value::Integer(integer) => Json::Number(integer.into()),
+            Value::Error(message) => {
+                Json::Object(Map::from_iter([("Error".into(), Json::String(message))]))
+            }
+            Value::String(string) | Value::SimpleString(string) => Json::String(string),
+            Value::Array(values) => Json::Array(values.into_iter().map(Json::from).collect()),
+            Value::Map(pairs) => Json::Object(
+                pairs
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let key = match key {
+                            Value::String(key) | Value::SimpleString(key) => key,
+                            key => Json::from(key).to_string(),
+                        };
+
+                        (key, Json::from(value))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Convert JSON into a value, the other way around `From<Value>`: strings
+/// are bulk [`Value::String`]s, objects are [`Value::Map`]s keyed by such
+/// strings, and objects with a sole `"Error"` key to a string are
+/// [`Value::Error`]s.
+///
+/// JSON numbers are only converted within the [`i64`] range of
+/// [`Value::Integer`]: floats, like booleans, have no RESP2 analog, and
+/// [`Value`] has no RESP3 doubles nor big numbers. The first JSON value which
+/// can't be converted is handed back as the error.
+///
+/// ```rust
+/// use serde_json::json;
+/// use squall_dot_io_resp::Value;
+///
+/// assert_eq!(
+///     Value::try_from(json!({"ttl": [60, null]})),
+///     Ok(Value::Map(vec![(
+///         Value::String("ttl".into()),
+///         Value::Array(vec![Value::Integer(60), Value::Nil])
+///     )]))
+/// );
+/// assert_eq!(Value::try_from(json!([1, 4.47])), Err(json!(4.47)));
+/// ```
+impl TryFrom<Json> for Value {
+    type Error = Json;
+
+    fn try_from(json: Json) -> Result<Self, Json> {
+        match json {
+            Json::Null => Ok(Value::Nil),
+            Json::Number(number) => match number.as_i64() {
+                Some(integer) => Ok(Value::Integer(integer)),
+                None => Err(Json::Number(number)),
+            },
+            Json::String(string) => Ok(Value::String(string)),
+            Json::Array(values) => values
+                .into_iter()
+                .map(Value::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+            Json::Object(object) => {
+                if let (1, Some(Json::String(message))) = (object.len(), object.get("Error")) {
+                    return Ok(Value::Error(message.clone()));
+                }
+
+                object
+                    .into_iter()
+                    .map(|(key, value)| Ok((Value::String(key), Value::try_from(value)?)))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Map)
+            }
+            json @ Json::Bool(_) => Err(json),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::Value;
+
+    #[test]
+    fn value_into_json() {
+        for (value, json) in [
+            (Value::Nil, json!(null)),
+            (Value::Integer(i64::MIN), json!(i64::MIN)),
+            (
+                Value::Error("ERR oops".into()),
+                json!({"Error": "ERR oops"}),
+            ),
+            (Value::String("Oh\r\nOh!".into()), json!("Oh\r\nOh!")),
+            (Value::SimpleString("OK".into()), json!("OK")),
+            (
+                Value::Array(vec![Value::Integer(1), Value::Array(vec![])]),
+                json!([1, []]),
+            ),
+            (
+                Value::Map(vec![
+                    (Value::SimpleString("a".into()), Value::Nil),
+                    (Value::Integer(1), Value::Integer(2)),
+                    (Value::Array(vec![Value::Nil]), Value::Map(vec![])),
+                ]),
+                json!({"a": null, "1": 2, "[null]": {}}),
+            ),
+        ] {
+            assert_eq!(serde_json::Value::from(value.clone()), json, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn value_try_from_json() {
+        for (json, value) in [
+            (json!(null), Value::Nil),
+            (json!(-447), Value::Integer(-447)),
+            (
+                json!({"Error": "ERR oops"}),
+                Value::Error("ERR oops".into()),
+            ),
+            (json!("OK"), Value::String("OK".into())),
+            (
+                json!([1, [null]]),
+                Value::Array(vec![Value::Integer(1), Value::Array(vec![Value::Nil])]),
+            ),
+            (
+                json!({"Error": 1}),
+                Value::Map(vec![(Value::String("Error".into()), Value::Integer(1))]),
+            ),
+            (
+                json!({"a": {"b": []}}),
+                Value::Map(vec![(
+                    Value::String("a".into()),
+                    Value::Map(vec![(Value::String("b".into()), Value::Array(vec![]))]),
+                )]),
+            ),
+        ] {
+            assert_eq!(Value::try_from(json.clone()), Ok(value), "{json}");
+        }
+    }
+
+    #[test]
+    fn value_try_from_json_without_analog() {
+        for (json, error) in [
+            (json!(true), json!(true)),
+            (json!(4.47), json!(4.47)),
+            (json!(u64::MAX), json!(u64::MAX)),
+            (json!([1, [false]]), json!(false)),
+            (json!({"a": 1.5}), json!(1.5)),
+        ] {
+            assert_eq!(Value::try_from(json.clone()), Err(error), "{json}");
+        }
+    }
+
+    #[test]
+    fn value_json_round_trip() {
+        let value = Value::Array(vec![
+            Value::Nil,
+            Value::Integer(447),
+            Value::Error("ERR oops".into()),
+            Value::String("Hourly".into()),
+            Value::Map(vec![
+                (Value::String("a".into()), Value::Array(vec![])),
+                (Value::String("b".into()), Value::Map(vec![])),
+            ]),
+        ]);
+
+        assert_eq!(
+            Value::try_from(serde_json::Value::from(value.clone())),
+            Ok(value)
+        );
+    }
+}
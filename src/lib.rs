@@ -25,9 +25,65 @@
 //! [^resp_spec_link]: <https://redis.io/docs/reference/protocol-spec/>
 //!
 //! [^pull_request_link]: <https://github.com/SalathielGenese/resp/compare/>
+//!
+//! ## Features
+//!
+//! + `std` _(default)_: implement [`std::error::Error`] for [`Error`], and
+//!   provide [`Value::from_reader`]. Without it, this crate is `#![no_std]`
+//!   and only needs the `alloc` crate.
+//! + `tokio-codec`: provide `codec::RespCodec`, framing values in
+//!   [Tokio](https://tokio.rs) byte streams. Implies `std`.
+//! + `futures`: provide `futures::read_value`, reading values off
+//!   [futures](https://docs.rs/futures)' `AsyncRead` readers. Implies `std`.
+//! + `serde`: implement [serde](https://serde.rs)'s `Serialize` and
+//!   `Deserialize` for [`Value`], deserialize [`Value`]s into any type with
+//!   `Value::deserialize_into`, and serialize any type into a [`Value`] with
+//!   `to_value`.
+//! + `json`: convert [`Value`]s from and into `serde_json::Value`s.
+//! + `bytes`: provide `ValueBytes`, whose strings share the
+//!   [`bytes`](https://docs.rs/bytes) buffer they are parsed from.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "tokio-codec")]
+pub use codec::RespCodec;
 pub use error::{Error, Node};
-pub use value::{Value, ValueResult};
+pub use hello::HelloInfo;
+pub use options::{EncodeOptions, ParseOptions};
+pub use scratch::Scratch;
+#[cfg(feature = "serde")]
+pub use ser::to_value;
+pub use value::{Attributes, Value, ValueResult};
+#[cfg(feature = "bytes")]
+pub use value_bytes::ValueBytes;
+pub use value_ref::ValueRef;
+pub use visitor::Visitor;
+pub use writer::RespWriter;
 
+#[cfg(feature = "tokio-codec")]
+pub mod codec;
+#[cfg(feature = "serde")]
+pub mod de;
 pub mod error;
+#[cfg(feature = "std")]
+mod framer;
+#[cfg(feature = "futures")]
+pub mod futures;
+pub mod hello;
+#[cfg(feature = "json")]
+pub mod json;
+#[doc(hidden)]
+pub mod macros;
+pub mod options;
+mod parser;
+pub mod scratch;
+#[cfg(feature = "serde")]
+pub mod ser;
 pub mod value;
+#[cfg(feature = "bytes")]
+pub mod value_bytes;
+pub mod value_ref;
+pub mod visitor;
+pub mod writer;
@@ -0,0 +1,123 @@
+use crate::value::Value;
+
+#[doc(hidden)]
+pub use alloc::vec;
+
+/// Build a [`Value`] with a JSON-like syntax.
+///
+/// + `nil` gives [`Value::Nil`];
+/// + string literals and expressions (`&str` or [`String`]) give bulk
+///   [`Value::String`]s;
+/// + integer literals and expressions give [`Value::Integer`]s;
+/// + any other expression convertible into a [`Value`] is converted;
+/// + `[...]` gives a [`Value::Array`] of its comma-separated entries, which
+///   can themselves be any of the above, nested arrays included.
+///
+/// # Examples
+///
+/// ```rust
+/// use squall_dot_io_resp::{resp, Value};
+///
+/// assert_eq!(resp!(nil), Value::Nil);
+/// assert_eq!(resp!(42), Value::Integer(42));
+/// assert_eq!(
+///     resp!(["GET", "key"]),
+///     Value::Array(vec![Value::String("GET".into()), Value::String("key".into())])
+/// );
+/// assert_eq!(
+///     resp!([1, [nil, "A"]]),
+///     Value::Array(vec![
+///         Value::Integer(1),
+///         Value::Array(vec![Value::Nil, Value::String("A".into())]),
+///     ])
+/// );
+/// ```
+#[macro_export]
+macro_rules! resp {
+    (nil) => {
+        $crate::Value::Nil
+    };
+    ([ $( $entries:tt )* ]) => {
+        $crate::resp!(@array [] $( $entries )*)
+    };
+    // Munch array entries one by one, as they may span several tokens.
+    (@array [ $( $done:expr, )* ]) => {
+        $crate::Value::Array($crate::macros::vec![ $( $done, )* ])
+    };
+    (@array [ $( $done:expr, )* ] nil $( , $( $rest:tt )* )?) => {
+        $crate::resp!(@array [ $( $done, )* $crate::Value::Nil, ] $( $( $rest )* )?)
+    };
+    (@array [ $( $done:expr, )* ] [ $( $inner:tt )* ] $( , $( $rest:tt )* )?) => {
+        $crate::resp!(
+            @array [ $( $done, )* $crate::resp!([ $( $inner )* ]), ] $( $( $rest )* )?
+        )
+    };
+    (@array [ $( $done:expr, )* ] $next:expr $( , $( $rest:tt )* )?) => {
+        $crate::resp!(@array [ $( $done, )* $crate::resp!($next), ] $( $( $rest )* )?)
+    };
+    ($other:expr) => {
+        $crate::macros::IntoValue::into_value($other)
+    };
+}
+
+/// Conversion used by [`resp!`], which also covers `&str`.
+///
+/// (`From<&str>` can't be implemented for [`Value`], as it would clash with
+/// the parsing `TryFrom<&str>` implementation.)
+#[doc(hidden)]
+pub trait IntoValue {
+    fn into_value(self) -> Value;
+}
+
+impl<T: Into<Value>> IntoValue for T {
+    fn into_value(self) -> Value {
+        self.into()
+    }
+}
+
+impl IntoValue for &str {
+    fn into_value(self) -> Value {
+        Value::String(self.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn resp_build_scalars() {
+        let key = String::from("key");
+
+        assert_eq!(crate::resp!(nil), Value::Nil);
+        assert_eq!(crate::resp!(42), Value::Integer(42));
+        assert_eq!(crate::resp!(-1i64), Value::Integer(-1));
+        assert_eq!(crate::resp!("GET"), Value::String("GET".into()));
+        assert_eq!(crate::resp!(key), Value::String("key".into()));
+        assert_eq!(
+            crate::resp!(Value::Error("Oh oh!".into())),
+            Value::Error("Oh oh!".into())
+        );
+    }
+
+    #[test]
+    fn resp_build_arrays() {
+        assert_eq!(crate::resp!([]), Value::Array(vec![]));
+        assert_eq!(
+            crate::resp!(["SET", "key", 10,]),
+            Value::Array(vec![
+                Value::String("SET".into()),
+                Value::String("key".into()),
+                Value::Integer(10),
+            ])
+        );
+        assert_eq!(
+            crate::resp!([[1, -2], nil, [["A"]]]),
+            Value::Array(vec![
+                Value::Array(vec![Value::Integer(1), Value::Integer(-2)]),
+                Value::Nil,
+                Value::Array(vec![Value::Array(vec![Value::String("A".into())])]),
+            ])
+        );
+    }
+}
@@ -0,0 +1,98 @@
+/// Knobs tuning how RESP strings are parsed.
+///
+/// The [`Default`] options are the lenient ones used by [`super::Value`]'s
+/// `TryFrom<&str>` implementation, so that only the fields you care about
+/// need to be spelled out:
+///
+/// ```rust
+/// use squall_dot_io_resp::ParseOptions;
+///
+/// let options = ParseOptions {
+///     strict: true,
+///     ..ParseOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    /// Reject any byte left after the top-level value.
+    ///
+    /// When disabled (the default), trailing bytes are ignored, which suits
+    /// streams where the next value follows right after. When enabled, the
+    /// parse fails with [`super::Error::Unexpected`] pointing at the first
+    /// leftover byte.
+    pub strict: bool,
+
+    /// Accept a bare `\n` as line terminator, besides `\r\n`.
+    ///
+    /// This suits peers which aren't strictly RESP compliant. It applies to
+    /// the lines of simple strings, errors and integers, as well as to the
+    /// headers of bulk strings and aggregates, RESP3 streamed ones, their
+    /// chunks and `.` terminator included: the payload of bulk strings and
+    /// chunks is still expected to end with `\r\n`. When disabled (the default), a
+    /// bare `\n` is an [`super::Error::Unexpected`] byte, or an
+    /// [`super::Error::Type`] one in integers.
+    pub bare_lf: bool,
+
+    /// Reject arrays announcing more entries than that, and maps announcing
+    /// more key/value pairs than that.
+    ///
+    /// The parse then fails with an [`super::Error::Overflow`] as soon as the
+    /// header is read, rather than after reading entries until the RESP
+    /// string runs dry. When `None` (the default), any count is accepted.
+    pub max_array_len: Option<usize>,
+
+    /// Reject arrays, maps and attributes nested into more than that many
+    /// others, empty ones included.
+    ///
+    /// The parse itself handles any nesting, but the [`super::Value`]s it
+    /// builds are dropped, cloned, compared and formatted recursively: the
+    /// limit keeps them from overflowing the call stack. The parse fails with
+    /// an [`super::Error::Overflow`] pointing at the header of the first
+    /// aggregate too deep. Defaults to
+    /// [`ParseOptions::DEFAULT_MAX_DEPTH`]; when `None`, any nesting is
+    /// accepted.
+    pub max_depth: Option<usize>,
+}
+
+impl ParseOptions {
+    /// The default [`ParseOptions::max_depth`], way past the nesting of any
+    /// reply, yet safe to handle on a thread with a small stack.
+    pub const DEFAULT_MAX_DEPTH: usize = 512;
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            strict: false,
+            bare_lf: false,
+            max_array_len: None,
+            max_depth: Some(ParseOptions::DEFAULT_MAX_DEPTH),
+        }
+    }
+}
+
+/// Knobs tuning how values are serialized.
+///
+/// The [`Default`] options are the ones of [`super::Value`]'s [`Display`]
+/// implementation and of [`super::Value::to_resp_bytes`]: they give RESP2
+/// compatible output wherever RESP2 has a form for the value.
+///
+/// [`Display`]: core::fmt::Display
+///
+/// ```rust
+/// use squall_dot_io_resp::{EncodeOptions, Value};
+///
+/// let resp3 = EncodeOptions { resp3: true };
+///
+/// assert_eq!(Value::Nil.to_resp_bytes_with(&resp3), b"_\r\n");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EncodeOptions {
+    /// Write the RESP3 forms of values which have one.
+    ///
+    /// So far, this only applies to [`super::Value::Nil`], written as the
+    /// RESP3 null, `_\r\n`. When disabled (the default), it is written as
+    /// the RESP2 null bulk string, `$-1\r\n`.
+    /// Either is parsed back as a [`super::Value::Nil`].
+    pub resp3: bool,
+}
@@ -0,0 +1,680 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::IntErrorKind;
+
+use memchr::memchr2;
+
+use crate::error::Error;
+use crate::options::ParseOptions;
+use crate::visitor::Visitor;
+use crate::Node::{
+    self, ARRAY, ATTRIBUTE, BULK_STRING, ERROR, INTEGER, MAP, NIL, SIMPLE_STRING, SIZE, UNKNOWN,
+};
+
+#[derive(Debug)]
+struct Input<'a> {
+    /// String range to be processed.
+    source: &'a str,
+    /// Bytes count of this range first [`char`], in the original [`&str`].
+    position: usize,
+    /// Whether a bare `\n` ends lines, as [`ParseOptions::bare_lf`] has it.
+    bare_lf: bool,
+}
+
+/// Outcome of looking for the `\r\n` sequence ending a line.
+#[derive(Debug)]
+enum Line {
+    /// The line ends at that index, with a line terminator of that length:
+    /// `\r\n`, or a bare `\n` if allowed.
+    End(usize, usize),
+    /// A `\r` or `\n` which isn't part of a `\r\n` was found at that index.
+    Stray(usize),
+    /// The source ends before the line does.
+    Open,
+}
+
+impl Input<'_> {
+    /// Find the end of the line this input starts with.
+    ///
+    /// The bytes are walked once, up to the first `\r` or `\n`: whether it
+    /// ends the line only depends on the byte right after it.
+    fn scan_line(&self) -> Line {
+        let source = self.source.as_bytes();
+
+        match memchr2(b'\r', b'\n', source) {
+            Some(i) if b'\n' == source[i] && self.bare_lf => Line::End(i, 1),
+            Some(i) if b'\n' == source[i] => Line::Stray(i),
+            Some(i) => match source.get(i + 1) {
+                Some(b'\n') => Line::End(i, 2),
+                Some(_) => Line::Stray(i),
+                None => Line::Open,
+            },
+            None => Line::Open,
+        }
+    }
+}
+
+/// What the parser reads in one step.
+#[derive(Debug)]
+enum Token<'a> {
+    Nil,
+    Integer(i64),
+    Error(&'a str),
+    String(&'a str),
+    SimpleString(&'a str),
+    /// The header of an array, with its entries count.
+    Array(usize),
+    /// The header of a RESP3 map, with its key/value pairs count.
+    Map(usize),
+    /// The header of a RESP3 attribute, with its key/value pairs count.
+    Attribute(usize),
+    /// The header of a RESP3 streamed array, whose entries count is unknown.
+    StreamedArray,
+    /// The `.` terminator of a RESP3 streamed array.
+    End,
+    /// The header of a RESP3 streamed string, whose chunks are read as
+    /// [`Token::String`]s until an empty one.
+    StreamedString,
+}
+
+impl Token<'_> {
+    /// The node of an aggregate header: [`MAP`], [`ATTRIBUTE`] or [`ARRAY`].
+    fn aggregate_node(&self) -> Node {
+        match self {
+            Token::Map(_) => MAP,
+            Token::Attribute(_) => ATTRIBUTE,
+            _ => ARRAY,
+        }
+    }
+}
+
+type TokenResult<'a> = (Result<Token<'a>, Error>, usize);
+
+/// An array, map or attribute being read.
+#[derive(Debug)]
+struct Aggregate {
+    /// Either [`ARRAY`], [`MAP`] or [`ATTRIBUTE`].
+    node: Node,
+    /// Entries count left to read, keys and values alike for maps: `None` for
+    /// RESP3 streamed arrays.
+    left: Option<usize>,
+}
+
+impl Aggregate {
+    fn end<'a>(&self, visitor: &mut impl Visitor<'a>) {
+        match self.node {
+            MAP => visitor.on_map_end(),
+            ATTRIBUTE => visitor.on_attribute_end(),
+            _ => visitor.on_array_end(),
+        }
+    }
+}
+
+/// Parse the RESP value found at the start of `source` bytes with `parse`,
+/// these bytes being valid UTF-8 up to the end of that value.
+///
+/// Should the parsing stop on invalid UTF-8, the offending byte is reported
+/// as an [`Error::Unexpected`] error, or as an [`Error::Incomplete`] one when
+/// the bytes end in the middle of a UTF-8 sequence.
+pub(crate) fn parse_utf8<'a, T>(
+    source: &'a [u8],
+    parse: impl FnOnce(&'a str) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let error = match core::str::from_utf8(source) {
+        Ok(source) => return parse(source),
+        Err(error) => error,
+    };
+    let index = error.valid_up_to();
+    // The bytes up to `index` were just checked to be valid UTF-8.
+    let valid = core::str::from_utf8(&source[..index]).unwrap_or_default();
+
+    match parse(valid) {
+        Err(Error::Incomplete { node, .. }) => match error.error_len() {
+            Some(_) => Err(Error::of_unexpected(node, index, source[index])),
+            None => Err(Error::of_incomplete(node, source.len())),
+        },
+        result => result,
+    }
+}
+
+/// Parse the RESP value found at the start of `source`, reporting its tokens
+/// to `visitor`, and return the number of bytes it spans.
+pub(crate) fn visit<'a>(source: &'a str, visitor: &mut impl Visitor<'a>) -> Result<usize, Error> {
+    visit_with(source, visitor, &ParseOptions::default())
+}
+
+/// Same as [`visit`], tuned by `options`.
+///
+/// Nested arrays and maps are handled with an explicit stack of the entries
+/// count left to read in each open [`Aggregate`], rather than with recursion,
+/// so that parsing deeply nested RESP strings cannot overflow the call stack.
+/// Visitors building values out of them may still do so, when dropping them
+/// for one: the stack is capped by [`ParseOptions::max_depth`]. RESP3
+/// streamed arrays are stacked as `None`, and stay open until their `.`
+/// terminator is read.
+/// RESP3 streamed strings are flagged as `streaming`, and only `;` chunks are
+/// read until the empty one ending them.
+/// RESP3 attributes are stacked as maps are, but don't count as an entry of
+/// the aggregate they are found in: the value they annotate, read next, does.
+pub(crate) fn visit_with<'a>(
+    source: &'a str,
+    visitor: &mut impl Visitor<'a>,
+    options: &ParseOptions,
+) -> Result<usize, Error> {
+    let mut aggregates: Vec<Aggregate> = vec![];
+    let mut streaming = false;
+    // Whether the last value read was an attribute, which must be followed by
+    // the value it annotates.
+    let mut annotating = false;
+    let mut offset = 0;
+
+    loop {
+        let input = Input {
+            position: offset,
+            source: &source[offset..],
+            bare_lf: options.bare_lf,
+        };
+
+        if input.source.is_empty() && streaming {
+            return Err(Error::of_incomplete(BULK_STRING, offset));
+        }
+        if let (true, Some(aggregate)) = (input.source.is_empty(), aggregates.last()) {
+            return Err(Error::of_incomplete(aggregate.node.clone(), offset));
+        }
+
+        let position = offset;
+        let token = match if streaming {
+            extract_chunk(input)
+        } else {
+            read_token(input)
+        } {
+            (Ok(token), size) => {
+                offset += size;
+                token
+            }
+            (Err(error), _) => return Err(error),
+        };
+
+        let annotated = core::mem::take(&mut annotating);
+
+        match token {
+            Token::Array(len) | Token::Map(len) | Token::Attribute(len)
+                if options.max_array_len.is_some_and(|max| max < len) =>
+            {
+                return Err(Error::of_overflow(token.aggregate_node(), position + 1));
+            }
+            Token::Array(_) | Token::Map(_) | Token::Attribute(_) | Token::StreamedArray
+                if options.max_depth.is_some_and(|max| max <= aggregates.len()) =>
+            {
+                return Err(Error::of_overflow(token.aggregate_node(), position));
+            }
+            Token::Nil => visitor.on_nil(),
+            Token::Integer(integer) => visitor.on_integer(integer),
+            Token::Error(message) => visitor.on_error(message),
+            Token::String("") if streaming => {
+                streaming = false;
+                visitor.on_streamed_string_end();
+            }
+            Token::String(chunk) if streaming => {
+                visitor.on_string_chunk(chunk);
+                continue;
+            }
+            Token::String(string) => visitor.on_string(string),
+            Token::SimpleString(string) => visitor.on_simple_string(string),
+            Token::Array(len) => {
+                visitor.on_array_start(len);
+
+                if 0 < len {
+                    aggregates.push(Aggregate {
+                        node: ARRAY,
+                        left: Some(len),
+                    });
+                    continue;
+                }
+
+                visitor.on_array_end();
+            }
+            Token::Map(len) => {
+                let Some(left) = len.checked_mul(2) else {
+                    return Err(Error::of_overflow(SIZE, position + 1));
+                };
+
+                visitor.on_map_start(len);
+
+                if 0 < len {
+                    aggregates.push(Aggregate {
+                        node: MAP,
+                        left: Some(left),
+                    });
+                    continue;
+                }
+
+                visitor.on_map_end();
+            }
+            Token::Attribute(len) => {
+                let Some(left) = len.checked_mul(2) else {
+                    return Err(Error::of_overflow(SIZE, position + 1));
+                };
+
+                visitor.on_attribute_start(len);
+
+                if 0 < len {
+                    aggregates.push(Aggregate {
+                        node: ATTRIBUTE,
+                        left: Some(left),
+                    });
+                } else {
+                    visitor.on_attribute_end();
+                    annotating = true;
+                }
+                continue;
+            }
+            Token::StreamedArray => {
+                visitor.on_streamed_array_start();
+                aggregates.push(Aggregate {
+                    node: ARRAY,
+                    left: None,
+                });
+                continue;
+            }
+            Token::StreamedString => {
+                visitor.on_streamed_string_start();
+                streaming = true;
+                continue;
+            }
+            Token::End if annotated => return Err(Error::of_unexpected(ATTRIBUTE, position, b'.')),
+            Token::End => match aggregates.last() {
+                Some(Aggregate { left: None, .. }) => {
+                    aggregates.pop();
+                    visitor.on_array_end();
+                }
+                _ => return Err(Error::of_unexpected(UNKNOWN, position, b'.')),
+            },
+        }
+
+        // A value was completed: close the aggregates it completes in turn.
+        loop {
+            match aggregates.last_mut() {
+                None => return Ok(offset),
+                Some(Aggregate { left: Some(1), .. }) => {
+                    if let Some(aggregate) = aggregates.pop() {
+                        aggregate.end(visitor);
+
+                        // The annotated value is still to be read.
+                        if aggregate.node == ATTRIBUTE {
+                            annotating = true;
+                            break;
+                        }
+                    }
+                }
+                Some(Aggregate {
+                    left: Some(len), ..
+                }) => {
+                    *len -= 1;
+                    break;
+                }
+                Some(Aggregate { left: None, .. }) => break,
+            }
+        }
+    }
+}
+
+fn read_token(input: Input) -> TokenResult {
+    match input.source.chars().next() {
+        Some('*') => extract_array(input),
+        Some('%') => extract_map(input),
+        Some('|') => extract_attribute(input),
+        Some('-') => extract_error(input),
+        Some(':') => extract_integer(input),
+        Some('$') => extract_bulk_string(input),
+        Some('+') => extract_simple_string(input),
+        Some('.') => extract_end(input),
+        Some('_') => extract_null(input),
+        None => (Err(Error::of_incomplete(UNKNOWN, input.position)), 0),
+        _ => {
+            let byte = input.source.as_bytes()[0];
+            (Err(Error::of_unexpected(UNKNOWN, input.position, byte)), 0)
+        }
+    }
+}
+
+/// Only the array header is read here: its entries are read by the [`visit`]
+/// loop. The RESP2 null array, `*-1\r\n`, is read as the null bulk string
+/// is.
+fn extract_array(input: Input) -> TokenResult {
+    if let Some(result) = extract_nil(Input { ..input }) {
+        return result;
+    }
+    if let Some(result) = extract_streamed(&input, Token::StreamedArray) {
+        return result;
+    }
+
+    extract_len(input, Token::Array)
+}
+
+/// Read the entries count of an aggregate header into a `token`.
+///
+/// Negative counts, once nulls are read apart, are out of range: they are
+/// reported as an [`Error::Overflow`], as bulk strings negative sizes are.
+/// RESP3 maps have no null, so that `%-1\r\n` is out of range as well.
+fn extract_len<'a>(input: Input<'a>, token: fn(usize) -> Token<'a>) -> TokenResult<'a> {
+    let position = input.position + 1;
+
+    match extract_integer(input) {
+        (Ok(Token::Integer(len)), size) => match usize::try_from(len) {
+            Ok(len) => (Ok(token(len)), size),
+            Err(_) => (Err(Error::of_overflow(SIZE, position)), position),
+        },
+        r#else => r#else,
+    }
+}
+
+/// Only the map header is read here: its keys and values are read by the
+/// [`visit`] loop.
+fn extract_map(input: Input) -> TokenResult {
+    extract_len(input, Token::Map)
+}
+
+/// Only the attribute header is read here: its keys and values are read by
+/// the [`visit`] loop, as map ones are.
+fn extract_attribute(input: Input) -> TokenResult {
+    extract_len(input, Token::Attribute)
+}
+
+/// Whether the terminator closes a streamed array is checked by the [`visit`]
+/// loop.
+fn extract_end(input: Input) -> TokenResult {
+    match input.scan_line() {
+        Line::End(1, len) => (Ok(Token::End), 1 + len),
+        Line::Open if ".\r".starts_with(input.source) => {
+            let position = input.position + input.source.len();
+            (Err(Error::of_incomplete(ARRAY, position)), position)
+        }
+        _ => {
+            let position = input.position + 1;
+            let byte = input.source.as_bytes()[1];
+            (Err(Error::of_unexpected(ARRAY, position, byte)), position)
+        }
+    }
+}
+
+/// The RESP3 null, `_\r\n`, is read as the null bulk string is.
+fn extract_null(input: Input) -> TokenResult {
+    match input.scan_line() {
+        Line::End(1, len) => (Ok(Token::Nil), 1 + len),
+        Line::Open if "_\r".starts_with(input.source) => {
+            let position = input.position + input.source.len();
+            (Err(Error::of_incomplete(NIL, position)), position)
+        }
+        _ => {
+            let i = 1 + usize::from(input.source[1..].starts_with('\r'));
+            let position = input.position + i;
+            let byte = input.source.as_bytes()[i];
+            (Err(Error::of_unexpected(NIL, position, byte)), position)
+        }
+    }
+}
+
+fn extract_error(input: Input) -> TokenResult {
+    match extract_simple_string(input) {
+        (Ok(Token::SimpleString(message)), size) => (Ok(Token::Error(message)), size),
+        r#else => r#else,
+    }
+}
+
+/// Integers are an optional `+` or `-` sign followed by at least one ASCII
+/// digit, as the RESP spec has it: anything else (e.g. spaces, around the
+/// sign or the digits, or no digits at all) is an [`Error::Type`], indexed
+/// past the type byte.
+///
+/// Digits are always read in base 10, leading zeros included: `:42\r\n`,
+/// `:+42\r\n`, `:-42\r\n` and `:0042\r\n` are fine, while `:0x2A\r\n`,
+/// `:4_2\r\n`, `:4.2e1\r\n` and `:--42\r\n` are [`Error::Type`]s.
+///
+/// Sizes (of bulk strings, arrays, maps...) are read here as well, but only
+/// take the `-` sign, as the spec has no `+` for them: e.g. `$+4\r\n` is an
+/// [`Error::Type`] while `:+4\r\n` is fine.
+fn extract_integer(input: Input) -> TokenResult {
+    let node = match input.source.as_bytes().first() {
+        Some(b':') => INTEGER,
+        Some(_) => SIZE,
+        None => return (Err(Error::of_incomplete(UNKNOWN, input.position)), 0),
+    };
+    let position = input.position + 1;
+
+    match input.scan_line() {
+        Line::End(i, _) if !is_integer(&input.source[1..i], &node) => {
+            (Err(Error::of_type(node, position)), position)
+        }
+        Line::End(i, len) => match input.source[1..i].parse::<i64>() {
+            Ok(value) => (Ok(Token::Integer(value)), i + len),
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow
+                ) =>
+            {
+                (Err(Error::of_overflow(node, position)), position)
+            }
+            _ => (Err(Error::of_type(node, position)), position),
+        },
+        Line::Stray(_) => (Err(Error::of_type(node, position)), position),
+        Line::Open => {
+            let position = input.position + input.source.len();
+            (Err(Error::of_incomplete(node, position)), position)
+        }
+    }
+}
+
+fn is_integer(source: &str, node: &Node) -> bool {
+    let signs: &[char] = match node {
+        INTEGER => &['+', '-'],
+        _ => &['-'],
+    };
+    let digits = source.strip_prefix(signs).unwrap_or(source);
+
+    !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Read the `-1` size of null bulk strings and arrays, `None` if `input`
+/// doesn't start with one.
+///
+/// Malformed line endings past that size are reported with the [`NIL`] node,
+/// rather than as sizes which are not numbers.
+fn extract_nil(input: Input) -> Option<TokenResult> {
+    let rest = input.source.get(1..)?.strip_prefix("-1")?;
+
+    if rest.starts_with(|char: char| char.is_ascii_digit()) {
+        return None;
+    }
+
+    let result = if rest.starts_with("\r\n") {
+        (Ok(Token::Nil), 5)
+    } else if input.bare_lf && rest.starts_with('\n') {
+        (Ok(Token::Nil), 4)
+    } else if "\r\n".starts_with(rest) {
+        let position = input.position + input.source.len();
+        (Err(Error::of_incomplete(NIL, position)), position)
+    } else {
+        let i = usize::from(rest.starts_with('\r'));
+        let position = input.position + 3 + i;
+        (
+            Err(Error::of_unexpected(NIL, position, rest.as_bytes()[i])),
+            position,
+        )
+    };
+
+    Some(result)
+}
+
+/// Read the `?` size of RESP3 streamed arrays and strings into a `token`,
+/// `None` if `input` doesn't start with one.
+///
+/// Malformed line endings past that size are left to be reported as sizes
+/// which are not numbers.
+fn extract_streamed<'a>(input: &Input, token: Token<'a>) -> Option<TokenResult<'a>> {
+    let rest = input.source.get(1..)?.strip_prefix('?')?;
+
+    if rest.starts_with("\r\n") {
+        Some((Ok(token), 4))
+    } else if input.bare_lf && rest.starts_with('\n') {
+        Some((Ok(token), 3))
+    } else {
+        None
+    }
+}
+
+/// Note that the size is sliced past as it was found on the wire, so that
+/// leading zeros (e.g. `$04\r\nOops\r\n`) are accepted, as they are for
+/// integers.
+fn extract_bulk_string(input: Input) -> TokenResult {
+    if let Some(result) = extract_nil(Input { ..input }) {
+        return result;
+    }
+    if let Some(result) = extract_streamed(&input, Token::StreamedString) {
+        return result;
+    }
+
+    match extract_integer(Input { ..input }) {
+        (Ok(Token::Integer(size)), start) => extract_payload(input, start, size),
+        r#else => r#else,
+    }
+}
+
+/// Read a `;` chunk of a RESP3 streamed string, which is sized as bulk
+/// strings are, but for the empty last chunk, which has no payload at all.
+fn extract_chunk(input: Input) -> TokenResult {
+    match input.source.as_bytes()[0] {
+        b';' => {}
+        byte => {
+            return (
+                Err(Error::of_unexpected(BULK_STRING, input.position, byte)),
+                0,
+            )
+        }
+    }
+
+    match extract_integer(Input { ..input }) {
+        (Ok(Token::Integer(0)), start) => (Ok(Token::String("")), start),
+        (Ok(Token::Integer(size)), start) => extract_payload(input, start, size),
+        r#else => r#else,
+    }
+}
+
+/// Read the `size` bytes payload found at `start`, past the header of a bulk
+/// string or chunk, and the `\r\n` sequence ending it.
+///
+/// Negative sizes, besides the null `-1` one, are out of range: they are
+/// reported as an [`Error::Overflow`], as sizes too large to be addressed
+/// are.
+///
+/// Payloads longer or shorter than `size` are told apart from complete ones
+/// by the bytes found `size` bytes past `start` alone: an [`Error::Size`] is
+/// reported at that very index whenever they aren't `\r\n`, whichever the
+/// payload length, e.g. at index 6 in `$2\r\nHello\r\n` and at index 9 in
+/// `$5\r\nOops\r\n`. Should the source end before (or right within) that
+/// `\r\n` sequence, the payload may yet be complete: it's then an
+/// [`Error::Incomplete`], e.g. in `$6\r\nOops\r\n`.
+fn extract_payload(input: Input, start: usize, size: i64) -> TokenResult {
+    let Some(end) = usize::try_from(size)
+        .ok()
+        .and_then(|size| start.checked_add(size))
+    else {
+        let position = input.position + 1;
+        return (Err(Error::of_overflow(SIZE, position)), position);
+    };
+
+    let rest = input.source.as_bytes().get(end..).unwrap_or_default();
+
+    if rest.starts_with(b"\r\n") {
+        (Ok(Token::String(&input.source[start..end])), end + 2)
+    } else if b"\r\n".starts_with(rest) {
+        let position = input.position + input.source.len();
+        (Err(Error::of_incomplete(BULK_STRING, position)), position)
+    } else {
+        let position = input.position + end;
+        (Err(Error::of_size(BULK_STRING, position)), position)
+    }
+}
+
+fn extract_simple_string(input: Input) -> TokenResult {
+    let node = match input.source.as_bytes().first() {
+        Some(b'+') => SIMPLE_STRING,
+        Some(_) => ERROR,
+        None => return (Err(Error::of_incomplete(UNKNOWN, input.position)), 0),
+    };
+
+    match input.scan_line() {
+        Line::End(i, len) => (Ok(Token::SimpleString(&input.source[1..i])), i + len),
+        Line::Stray(i) => {
+            let position = input.position + i;
+            let byte = input.source.as_bytes()[i];
+            (Err(Error::of_unexpected(node, position, byte)), position)
+        }
+        Line::Open => {
+            let position = input.position + input.source.len();
+            (Err(Error::of_incomplete(node, position)), position)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::options::ParseOptions;
+    use crate::visitor::Visitor;
+    use crate::Node::{ARRAY, UNKNOWN};
+    use crate::Value;
+
+    use super::{extract_integer, visit_with, Input};
+
+    /// Count the arrays opened, building nothing.
+    struct Depth(usize);
+
+    impl Visitor<'_> for Depth {
+        fn on_array_start(&mut self, _len: usize) {
+            self.0 += 1;
+        }
+    }
+
+    #[test]
+    fn parser_extract_integer_from_empty_source() {
+        let input = Input {
+            source: "",
+            position: 8,
+            bare_lf: false,
+        };
+
+        assert!(matches!(
+            extract_integer(input),
+            (
+                Err(Error::Incomplete {
+                    index: 8,
+                    node: UNKNOWN
+                }),
+                0
+            )
+        ));
+        assert_eq!(
+            Value::try_from("*2\r\n:1\r\n"),
+            Err(Error::of_incomplete(ARRAY, 8))
+        );
+    }
+
+    #[test]
+    fn parser_visit_deeply_nested_arrays_without_recursion() {
+        let depth = 1_000_000;
+        let source = format!("{}:1\r\n", "*1\r\n".repeat(depth));
+        let unlimited = ParseOptions {
+            max_depth: None,
+            ..ParseOptions::default()
+        };
+        let mut visitor = Depth(0);
+
+        assert_eq!(
+            visit_with(&source, &mut visitor, &unlimited),
+            Ok(source.len())
+        );
+        assert_eq!(visitor.0, depth);
+    }
+}
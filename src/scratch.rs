@@ -0,0 +1,157 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::value::{pairs, Value};
+
+/// A pool of buffers reused across parses by [`Value::parse_in`], rather
+/// than allocated anew each time.
+///
+/// Values parsed from a scratch are owned by the caller, as any other ones:
+/// nothing ties them to the scratch, which is only borrowed while parsing.
+/// Their strings and vectors go back to the pool once the values are handed
+/// to [`Scratch::recycle`], to be reused by the next parses. Values which
+/// are dropped instead simply free their buffers. The pool keeps its buffers
+/// until dropped.
+///
+/// ```rust
+/// use squall_dot_io_resp::{Scratch, Value};
+///
+/// let mut scratch = Scratch::new();
+///
+/// for source in ["*2\r\n+OK\r\n:1\r\n", "*1\r\n$5\r\nHello\r\n"] {
+///     let value = Value::parse_in(source, &mut scratch).unwrap();
+///
+///     assert_eq!(Value::try_from(source), Ok(value.clone()));
+///     scratch.recycle(value);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Scratch {
+    /// Emptied strings.
+    strings: Vec<String>,
+    /// Emptied arrays entries.
+    arrays: Vec<Vec<Value>>,
+    /// Emptied maps pairs.
+    maps: Vec<Vec<(Value, Value)>>,
+}
+
+impl Scratch {
+    /// Create an empty pool.
+    pub fn new() -> Scratch {
+        Scratch::default()
+    }
+
+    /// Take back the strings and vectors of `value`, and of its descendants,
+    /// emptied.
+    pub fn recycle(&mut self, value: Value) {
+        let mut values = self.array();
+
+        values.push(value);
+
+        while let Some(value) = values.pop() {
+            match value {
+                Value::Nil | Value::Integer(_) => {}
+                Value::Error(mut string)
+                | Value::String(mut string)
+                | Value::SimpleString(mut string) => {
+                    string.clear();
+                    self.strings.push(string);
+                }
+                Value::Array(mut entries) => {
+                    values.append(&mut entries);
+                    self.arrays.push(entries);
+                }
+                Value::Map(mut entries) => {
+                    values.extend(entries.drain(..).flat_map(|(key, value)| [key, value]));
+                    self.maps.push(entries);
+                }
+            }
+        }
+
+        self.arrays.push(values);
+    }
+
+    /// Copy `source` into a pooled string, if any.
+    pub(crate) fn string(&mut self, source: &str) -> String {
+        match self.strings.pop() {
+            Some(mut string) => {
+                string.push_str(source);
+                string
+            }
+            None => source.into(),
+        }
+    }
+
+    /// Take an empty pooled vector for array entries, if any.
+    pub(crate) fn array(&mut self) -> Vec<Value> {
+        self.arrays.pop().unwrap_or_default()
+    }
+
+    /// Pair up consecutive `values` into a pooled vector, if any, as
+    /// [`pairs`] does.
+    pub(crate) fn map(&mut self, mut values: Vec<Value>) -> Vec<(Value, Value)> {
+        let Some(mut map) = self.maps.pop() else {
+            return pairs(values);
+        };
+        let mut entries = values.drain(..);
+
+        while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+            map.push((key, value));
+        }
+
+        drop(entries);
+        self.arrays.push(values);
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Scratch, Value};
+
+    #[test]
+    fn scratch_reuse_buffers() {
+        let mut scratch = Scratch::new();
+        let value = Value::parse_in("$5\r\nHello\r\n", &mut scratch).unwrap();
+        let Value::String(string) = &value else {
+            unreachable!()
+        };
+        let pointer = string.as_ptr();
+
+        scratch.recycle(value);
+
+        let value = Value::parse_in("+Bye\r\n", &mut scratch).unwrap();
+
+        assert_eq!(value, Value::SimpleString("Bye".into()));
+        assert_eq!(value.as_str().map(str::as_ptr), Some(pointer));
+    }
+
+    #[test]
+    fn scratch_parse_repeatedly() {
+        let mut scratch = Scratch::new();
+        let sources = [
+            "*3\r\n$5\r\nHello\r\n*1\r\n:1\r\n%1\r\n+a\r\n-b\r\n",
+            "%2\r\n*0\r\n$?\r\n;2\r\nOK\r\n;0\r\n$-1\r\n*1\r\n+c\r\n",
+            "*?\r\n+d\r\n|1\r\n+ttl\r\n:1\r\n$1\r\ne\r\n.\r\n",
+            "+f\r\n",
+        ];
+
+        for _ in 0..3 {
+            for source in sources {
+                let value = Value::parse_in(source, &mut scratch);
+
+                assert_eq!(value, Value::try_from(source), "{source:?}");
+                scratch.recycle(value.unwrap());
+            }
+        }
+
+        assert_eq!(
+            Value::parse_in("*2\r\n+g\r\n", &mut scratch),
+            Value::try_from("*2\r\n+g\r\n")
+        );
+        assert_eq!(
+            Value::parse_in("*1\r\n+h\r\n", &mut scratch),
+            Ok(Value::Array(vec![Value::SimpleString("h".into())]))
+        );
+    }
+}
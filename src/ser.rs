@@ -0,0 +1,607 @@
+//! Serialize [`Value`]s with [serde](https://serde.rs).
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt::Display;
+
+use serde::ser::{
+    Error, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+use crate::error::Error as TError;
+use crate::value::Value;
+
+/// Serialize values as the serde data model has it: [`Value::Nil`] as a
+/// none, integers as `i64`s, bulk and simple strings alike as strings, arrays
+/// as sequences and maps as maps.
+///
+/// Errors have no analog there: they are serialized as the externally tagged
+/// `Error` newtype variant of a `Value` enum, e.g. `{"Error":"ERR oops"}` in
+/// JSON.
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// let value = Value::Array(vec![
+///     Value::Nil,
+///     Value::Integer(447),
+///     Value::SimpleString("Hourly".into()),
+///     Value::Error("ERR oops".into()),
+/// ]);
+///
+/// assert_eq!(
+///     serde_json::to_string(&value).unwrap(),
+///     r#"[null,447,"Hourly",{"Error":"ERR oops"}]"#
+/// );
+/// ```
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Nil => serializer.serialize_none(),
+            Value::Integer(integer) => serializer.serialize_i64(*integer),
+            Value::Error(message) => {
+                serializer.serialize_newtype_variant("Value", 2, "Error", message)
+            }
+            Value::String(string) | Value::SimpleString(string) => serializer.serialize_str(string),
+            Value::Array(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            Value::Map(pairs) => {
+                let mut map = serializer.serialize_map(Some(pairs.len()))?;
+
+                for (key, value) in pairs {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// Serialize any `T` into a [`Value`], e.g. to encode it as RESP.
+///
+/// Strings are serialized as bulk [`Value::String`]s, integers as
+/// [`Value::Integer`]s, sequences, tuples and tuple structs as
+/// [`Value::Array`]s, and nones and units as [`Value::Nil`].
+///
+/// Maps and structs are serialized as [`Value::Map`]s, keyed by their field
+/// names for structs: these are RESP3 maps, which RESP2 peers don't
+/// understand. Besides, Redis conventions are followed for
+/// types RESP2 has no analog for: booleans are serialized as the `0` and `1`
+/// integers, and floats as bulk strings, e.g. `"0.75"` or `"inf"`, rather
+/// than as RESP3 doubles. Unit variants are serialized as their name, and
+/// other variants as a map of a sole entry, keyed by their name, which is how
+/// [`Value::deserialize_into`] reads them back.
+///
+/// Integers out of the [`i64`] range, and bytes which aren't valid UTF-8, are
+/// reported as [`TError::Serde`] errors, as are those of `T` itself.
+///
+/// ```rust
+/// use squall_dot_io_resp::{to_value, Value};
+///
+/// assert_eq!(
+///     to_value(&("Hourly", Some(447), None::<u8>)),
+///     Ok(Value::Array(vec![
+///         Value::String("Hourly".into()),
+///         Value::Integer(447),
+///         Value::Nil,
+///     ]))
+/// );
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, TError> {
+    value.serialize(ValueSerializer)
+}
+
+impl Error for TError {
+    fn custom<T: Display>(message: T) -> TError {
+        TError::Serde {
+            message: message.to_string(),
+        }
+    }
+}
+
+/// A [`Serializer`] building [`Value`]s, as [`to_value`] does.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ValueSerializer;
+
+impl ValueSerializer {
+    fn integer<T: TryInto<i64> + Display + Copy>(integer: T) -> Result<Value, TError> {
+        integer
+            .try_into()
+            .map(Value::Integer)
+            .map_err(|_| TError::custom(format!("integer `{integer}` out of the i64 range")))
+    }
+
+    /// Wrap the value of a non-unit variant into a map of a sole entry.
+    fn variant(variant: &'static str, value: Value) -> Value {
+        Value::Map(vec![(Value::String(variant.into()), value)])
+    }
+}
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = TError;
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializePairs;
+    type SerializeStruct = SerializePairs;
+    type SerializeStructVariant = SerializeVariant<SerializePairs>;
+
+    fn serialize_bool(self, boolean: bool) -> Result<Value, TError> {
+        Ok(Value::Integer(boolean.into()))
+    }
+
+    fn serialize_i8(self, integer: i8) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_i16(self, integer: i16) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_i32(self, integer: i32) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_i64(self, integer: i64) -> Result<Value, TError> {
+        Ok(Value::Integer(integer))
+    }
+
+    fn serialize_i128(self, integer: i128) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_u8(self, integer: u8) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_u16(self, integer: u16) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_u32(self, integer: u32) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_u64(self, integer: u64) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_u128(self, integer: u128) -> Result<Value, TError> {
+        ValueSerializer::integer(integer)
+    }
+
+    fn serialize_f32(self, float: f32) -> Result<Value, TError> {
+        Ok(Value::String(float.to_string()))
+    }
+
+    fn serialize_f64(self, float: f64) -> Result<Value, TError> {
+        Ok(Value::String(float.to_string()))
+    }
+
+    fn serialize_char(self, char: char) -> Result<Value, TError> {
+        Ok(Value::String(char.into()))
+    }
+
+    fn serialize_str(self, string: &str) -> Result<Value, TError> {
+        Ok(Value::String(string.into()))
+    }
+
+    fn serialize_bytes(self, bytes: &[u8]) -> Result<Value, TError> {
+        core::str::from_utf8(bytes)
+            .map(|string| Value::String(string.into()))
+            .map_err(TError::custom)
+    }
+
+    fn serialize_none(self) -> Result<Value, TError> {
+        Ok(Value::Nil)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, TError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, TError> {
+        Ok(Value::Nil)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, TError> {
+        Ok(Value::Nil)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Value, TError> {
+        Ok(Value::String(variant.into()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Value, TError> {
+        value.serialize(self)
+    }
+
+    /// The `Error` variant of [`Value`] itself is serialized back as a
+    /// [`Value::Error`].
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, TError> {
+        match (name, variant, to_value(value)?) {
+            ("Value", "Error", Value::String(message)) => Ok(Value::Error(message)),
+            (_, _, value) => Ok(ValueSerializer::variant(variant, value)),
+        }
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, TError> {
+        Ok(SerializeArray {
+            values: Vec::with_capacity(len.unwrap_or_default().min(4096)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, TError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<SerializeArray, TError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>, TError> {
+        Ok(SerializeVariant {
+            variant,
+            value: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializePairs, TError> {
+        Ok(SerializePairs {
+            pairs: Vec::with_capacity(len.unwrap_or_default().min(4096)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<SerializePairs, TError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializePairs>, TError> {
+        Ok(SerializeVariant {
+            variant,
+            value: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Build the [`Value::Array`] of a sequence, a tuple or a tuple struct.
+#[derive(Debug)]
+pub struct SerializeArray {
+    values: Vec<Value>,
+}
+
+impl SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = TError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TError> {
+        self.values.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, TError> {
+        Ok(Value::Array(self.values))
+    }
+}
+
+impl SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = TError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, TError> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = TError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, TError> {
+        SerializeSeq::end(self)
+    }
+}
+
+/// Build the [`Value::Map`] of a map or a struct.
+#[derive(Debug)]
+pub struct SerializePairs {
+    pairs: Vec<(Value, Value)>,
+    /// The key of the entry whose value is to be serialized next.
+    key: Option<Value>,
+}
+
+impl SerializeMap for SerializePairs {
+    type Ok = Value;
+    type Error = TError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), TError> {
+        self.key = Some(to_value(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| TError::custom("map value serialized before its key"))?;
+
+        self.pairs.push((key, to_value(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, TError> {
+        Ok(Value::Map(self.pairs))
+    }
+}
+
+impl SerializeStruct for SerializePairs {
+    type Ok = Value;
+    type Error = TError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), TError> {
+        self.pairs
+            .push((Value::String(key.into()), to_value(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, TError> {
+        SerializeMap::end(self)
+    }
+}
+
+/// Build the map of a sole entry of a tuple or struct variant, keyed by its
+/// name.
+#[derive(Debug)]
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    value: S,
+}
+
+impl SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = Value;
+    type Error = TError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TError> {
+        SerializeSeq::serialize_element(&mut self.value, value)
+    }
+
+    fn end(self) -> Result<Value, TError> {
+        let value = SerializeSeq::end(self.value)?;
+
+        Ok(ValueSerializer::variant(self.variant, value))
+    }
+}
+
+impl SerializeStructVariant for SerializeVariant<SerializePairs> {
+    type Ok = Value;
+    type Error = TError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), TError> {
+        SerializeStruct::serialize_field(&mut self.value, key, value)
+    }
+
+    fn end(self) -> Result<Value, TError> {
+        let value = SerializeMap::end(self.value)?;
+
+        Ok(ValueSerializer::variant(self.variant, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{to_value, Error, Value};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Role {
+        Master,
+        Replica { offset: u64 },
+        Sentinel(u8, u8),
+    }
+
+    /// Serialized with `serialize_bytes`, rather than as a sequence.
+    struct Bytes(&'static [u8]);
+
+    impl Serialize for Bytes {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Server {
+        name: String,
+        port: u16,
+        cluster: bool,
+        load: f64,
+        roles: Vec<Role>,
+        tags: Option<Vec<String>>,
+    }
+
+    #[test]
+    fn value_serialize_to_json() {
+        for (value, json) in [
+            (Value::Nil, "null"),
+            (Value::Integer(-447), "-447"),
+            (Value::Error("ERR oops".into()), r#"{"Error":"ERR oops"}"#),
+            (Value::String("Oh\r\nOh!".into()), r#""Oh\r\nOh!""#),
+            (Value::SimpleString("OK".into()), r#""OK""#),
+            (Value::Array(vec![]), "[]"),
+            (
+                Value::Array(vec![Value::Integer(1), Value::Array(vec![Value::Nil])]),
+                "[1,[null]]",
+            ),
+            (
+                Value::Map(vec![
+                    (Value::String("a".into()), Value::Integer(1)),
+                    (Value::SimpleString("b".into()), Value::Nil),
+                ]),
+                r#"{"a":1,"b":null}"#,
+            ),
+        ] {
+            assert_eq!(serde_json::to_string(&value).unwrap(), json, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn value_serialize_map_with_non_string_keys() {
+        let value = Value::Map(vec![(Value::Array(vec![]), Value::Nil)]);
+
+        assert!(serde_json::to_string(&value).is_err());
+    }
+
+    #[test]
+    fn value_to_value_struct() {
+        let server = Server {
+            name: "Hourly".into(),
+            port: 6379,
+            cluster: true,
+            load: 0.75,
+            roles: vec![
+                Role::Master,
+                Role::Replica { offset: 447 },
+                Role::Sentinel(1, 2),
+            ],
+            tags: None,
+        };
+        let value = to_value(&server).unwrap();
+        let string = |string: &str| Value::String(string.into());
+
+        assert_eq!(
+            value,
+            Value::Map(vec![
+                (string("name"), string("Hourly")),
+                (string("port"), Value::Integer(6379)),
+                (string("cluster"), Value::Integer(1)),
+                (string("load"), string("0.75")),
+                (
+                    string("roles"),
+                    Value::Array(vec![
+                        string("master"),
+                        Value::Map(vec![(
+                            string("replica"),
+                            Value::Map(vec![(string("offset"), Value::Integer(447))])
+                        )]),
+                        Value::Map(vec![(
+                            string("sentinel"),
+                            Value::Array(vec![Value::Integer(1), Value::Integer(2)])
+                        )]),
+                    ])
+                ),
+                (string("tags"), Value::Nil),
+            ])
+        );
+        assert_eq!(value.deserialize_into::<Server>(), Ok(server));
+    }
+
+    #[test]
+    fn value_to_value_vector() {
+        assert_eq!(
+            to_value(&vec![Some("SET"), None, Some("val")]),
+            Ok(Value::Array(vec![
+                Value::String("SET".into()),
+                Value::Nil,
+                Value::String("val".into()),
+            ]))
+        );
+        assert_eq!(
+            to_value(&BTreeMap::from([(1, vec![-1.5]), (2, vec![])])),
+            Ok(Value::Map(vec![
+                (
+                    Value::Integer(1),
+                    Value::Array(vec![Value::String("-1.5".into())])
+                ),
+                (Value::Integer(2), Value::Array(vec![])),
+            ]))
+        );
+    }
+
+    #[test]
+    fn value_to_value_round_trip() {
+        let value = Value::Array(vec![
+            Value::Nil,
+            Value::Integer(i64::MIN),
+            Value::Error("ERR oops".into()),
+            Value::String("Si vis pacem,\r\npara bellum".into()),
+            Value::Map(vec![(Value::Array(vec![]), Value::Map(vec![]))]),
+        ]);
+
+        assert_eq!(to_value(&value), Ok(value));
+        assert_eq!(
+            to_value(&Value::SimpleString("OK".into())),
+            Ok(Value::String("OK".into()))
+        );
+    }
+
+    #[test]
+    fn value_to_value_report_errors() {
+        assert_eq!(
+            to_value(&u64::MAX),
+            Err(Error::Serde {
+                message: "integer `18446744073709551615` out of the i64 range".into()
+            })
+        );
+        assert!(matches!(
+            to_value(&Bytes(b"O\xffK")),
+            Err(Error::Serde { .. })
+        ));
+    }
+}
@@ -1,32 +1,54 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter, Write};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::{ErrorKind, Read};
+
 use crate::error::Error as TError;
-use crate::Node::{ARRAY, BULK_STRING, ERROR, INTEGER, SIMPLE_STRING, SIZE, UNKNOWN};
+#[cfg(feature = "std")]
+use crate::framer::{Framer, Next};
+use crate::hello::HelloInfo;
+use crate::options::{EncodeOptions, ParseOptions};
+use crate::parser;
+use crate::scratch::Scratch;
+use crate::visitor::Visitor;
+use crate::Node::{SIMPLE_STRING, UNKNOWN};
 
 /// A wrapper type for a RESP value.
 ///
 /// This enum implements the `TryFrom` trait (`TryFrom<&str>`), to provide
 /// on-the-fly parsing and validation of RESP strings.
 ///
+/// Values are totally ordered, e.g. to sort them into a canonical order:
+/// first by variant, in the `Nil`, `Integer`, `Error`, `String`,
+/// `SimpleString`, `Array`, `Map` order they are declared in, then by payload.
+/// Strings compare bytewise, while arrays and maps compare entry by entry,
+/// the shorter first when one is a prefix of the other.
+///
 /// # Examples
 ///
 /// ```rust
 /// use squall_dot_io_resp::{
 ///     Node::{self, NIL, SIZE, ARRAY, ERROR, INTEGER, UNKNOWN, SIMPLE_STRING, BULK_STRING},
-///     Value::{self, Nil, Error, Array, String, Integer},
+///     Value::{self, Nil, Error, Array, String, Integer, SimpleString},
 ///     Error as VError,
 ///     ValueResult,
 /// };
 ///
 /// assert_eq!( // Empty RESP
 ///     "".try_into() as ValueResult,
-///     Err(VError::Unexpected {node: UNKNOWN, index: 0}));
+///     Err(VError::Incomplete {node: UNKNOWN, index: 0}));
 ///
 /// assert_eq!( // Unterminated number: missing "\r\n"
 ///     ":0".try_into() as ValueResult,
-///     Err(VError::Unexpected {node: INTEGER, index: 2}));
+///     Err(VError::Incomplete {node: INTEGER, index: 2}));
 ///
 /// assert_eq!( // Not enough elements in the array
 ///     "*2\r\n$-1\r\n".try_into() as ValueResult,
-///     Err(VError::Size {node: ARRAY, index: 9}));
+///     Err(VError::Incomplete {node: ARRAY, index: 9}));
 ///
 /// assert_eq!( // Longer bulk string: got more that 2-bytes
 ///     "$2\r\nHello\r\n".try_into() as ValueResult,
@@ -51,7 +73,7 @@ use crate::Node::{ARRAY, BULK_STRING, ERROR, INTEGER, SIMPLE_STRING, SIZE, UNKNO
 /// // JSON: "Nina Simone"
 /// assert_eq!(
 ///     Value::try_from("+Nina Simone\r\n"),
-///     Ok(String("Nina Simone".into()))
+///     Ok(SimpleString("Nina Simone".into()))
 /// );
 ///
 /// // JSON: "Lorem ipsum...\r\nDolor sit amet..."
@@ -67,320 +89,4109 @@ use crate::Node::{ARRAY, BULK_STRING, ERROR, INTEGER, SIMPLE_STRING, SIZE, UNKNO
 ///         Nil,
 ///         Integer(447),
 ///         Error("Oh oh!".into()),
-///         String("Hourly".into()),
+///         SimpleString("Hourly".into()),
 ///         String("Si vis pacem,\r\npara bellum".into())
 ///     ]))
 /// );
 ///
 /// // NOTE: Even recursive arrays - we leave that for you to try out.
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Value {
-    /// Denote the absence of value.
+    /// Denote the absence of value, which is the default one.
+    ///
+    /// It is parsed from the null bulk string, `$-1\r\n`, as well as from
+    /// the RESP2 null array, `*-1\r\n`, wherever they are nested.
+    #[default]
     Nil,
     /// Denote and integer value, wrapped as singleton tuple.
+    ///
+    /// It is parsed from an optional `+` or `-` sign followed by ASCII
+    /// digits, read in base 10, e.g. `:+5\r\n` or `:005\r\n`: spaces, radix
+    /// prefixes (`:0x5\r\n`), digit separators (`:1_000\r\n`) and exponents
+    /// (`:1e3\r\n`), among others, are rejected. Such malformed integers, as
+    /// well as empty ones (`:\r\n`), are [`TError::Type`] errors indexed at
+    /// the byte past `:`, where the number was expected.
     Integer(i64),
     /// Denote an error, wrapped as descriptive message string.
+    ///
+    /// The message may be empty, as parsed from `-\r\n`.
     Error(String),
-    /// Denote a string value, wrapped as singleton tuple.
+    /// Denote a bulk string value, wrapped as singleton tuple.
+    ///
+    /// It may be empty, as parsed from `$0\r\n\r\n`: the `\r\n` sequence
+    /// ending the payload is still required. RESP3 streamed strings, e.g.
+    /// `$?\r\n;2\r\nOK\r\n;0\r\n`, are parsed into it as well, with their
+    /// chunks concatenated.
     String(String),
+    /// Denote a simple string value, wrapped as singleton tuple.
+    ///
+    /// It is kept apart from [`Value::String`] so that the wire form it was
+    /// read from is known, and re-encoding gives back the same bytes. Like
+    /// bulk strings, it may be empty, as parsed from `+\r\n`. Unlike them, it
+    /// can't hold any `\r` nor `\n`: the first one found before the `\r\n`
+    /// sequence ending it is an [`TError::Unexpected`] byte, e.g. in
+    /// `+ab\r\r\n` or `+ab\n\r\n`.
+    SimpleString(String),
     /// Denote a non-nil list of values, wrapped as singleton vector of Value.
+    ///
+    /// RESP3 streamed arrays, e.g. `*?\r\n:1\r\n.\r\n`, are parsed into it
+    /// as well: they are written back with their entries count.
     Array(Vec<Value>),
+    /// Denote a RESP3 map, wrapped as vector of key/value pairs, in the order
+    /// they were read.
+    ///
+    /// RESP3 attributes, e.g. `|1\r\n+ttl\r\n:60\r\n`, are maps as well on
+    /// the wire, but aren't values of their own: they are skipped, unless read
+    /// with [`Value::parse_attributed`].
+    Map(Vec<(Value, Value)>),
+}
+
+/// Build a [`Value`] tree out of the parsed tokens.
+#[derive(Debug, Default)]
+struct Builder {
+    /// Arrays being filled, innermost last.
+    arrays: Vec<Vec<Value>>,
+    /// Chunks of the streamed string being read, concatenated.
+    chunks: String,
+    /// The attribute annotating the top-level value, if any.
+    attributes: Option<Attributes>,
+    /// The top-level value, once complete.
+    value: Option<Value>,
+    /// Buffers to build values with, empty unless parsing with
+    /// [`Value::parse_in`].
+    scratch: Scratch,
+}
+
+impl Builder {
+    fn push(&mut self, value: Value) {
+        match self.arrays.last_mut() {
+            Some(values) => values.push(value),
+            None => self.value = Some(value),
+        }
+    }
 }
 
-#[derive(Debug)]
-struct Input<'a> {
-    /// String range to be processed.
-    source: &'a str,
-    /// Bytes count of this range first [`char`], in the original [`&str`].
-    position: usize,
+impl Visitor<'_> for Builder {
+    fn on_nil(&mut self) {
+        self.push(Value::Nil)
+    }
+
+    fn on_integer(&mut self, integer: i64) {
+        self.push(Value::Integer(integer))
+    }
+
+    fn on_error(&mut self, message: &str) {
+        let message = self.scratch.string(message);
+        self.push(Value::Error(message))
+    }
+
+    fn on_string(&mut self, string: &str) {
+        let string = self.scratch.string(string);
+        self.push(Value::String(string))
+    }
+
+    fn on_simple_string(&mut self, string: &str) {
+        let string = self.scratch.string(string);
+        self.push(Value::SimpleString(string))
+    }
+
+    fn on_streamed_string_start(&mut self) {
+        self.chunks = self.scratch.string("");
+    }
+
+    fn on_string_chunk(&mut self, chunk: &str) {
+        self.chunks.push_str(chunk)
+    }
+
+    fn on_streamed_string_end(&mut self) {
+        let string = core::mem::take(&mut self.chunks);
+        self.push(Value::String(string))
+    }
+
+    fn on_array_start(&mut self, _len: usize) {
+        let values = self.scratch.array();
+        self.arrays.push(values)
+    }
+
+    fn on_array_end(&mut self) {
+        let values = self.arrays.pop().unwrap_or_default();
+        self.push(Value::Array(values))
+    }
+
+    fn on_map_start(&mut self, _len: usize) {
+        let values = self.scratch.array();
+        self.arrays.push(values)
+    }
+
+    fn on_map_end(&mut self) {
+        let values = self.arrays.pop().unwrap_or_default();
+        let pairs = self.scratch.map(values);
+        self.push(Value::Map(pairs))
+    }
+
+    fn on_attribute_start(&mut self, _len: usize) {
+        self.arrays.push(vec![])
+    }
+
+    /// Nested attributes are dropped.
+    fn on_attribute_end(&mut self) {
+        let values = self.arrays.pop().unwrap_or_default();
+
+        if self.arrays.is_empty() {
+            self.attributes = Some(pairs(values));
+        }
+    }
 }
 
-type InnerResult<'a> = (ValueResult<'a>, usize);
+/// Pair up consecutive `values`, dropping the last one if left alone.
+pub(crate) fn pairs<T>(values: Vec<T>) -> Vec<(T, T)> {
+    let mut values = values.into_iter();
+    let mut pairs = Vec::with_capacity(values.len() / 2);
+
+    while let (Some(key), Some(value)) = (values.next(), values.next()) {
+        pairs.push((key, value));
+    }
+
+    pairs
+}
 
 /// Just a type alias
 pub type ValueResult<'a> = Result<Value, <Value as TryFrom<&'a str>>::Error>;
 
+/// The key/value pairs of a RESP3 attribute, as read by
+/// [`Value::parse_attributed`].
+pub type Attributes = Vec<(Value, Value)>;
+
+/// Ignore the parsed tokens, for [`Value::validate`].
+struct Validator;
+
+impl Visitor<'_> for Validator {}
+
 impl TryFrom<&str> for Value {
     type Error = TError;
 
-    fn try_from(source: &str) -> ValueResult {
-        Value::internal_try_from(Input {
-            position: 0,
-            source,
-        })
-        .0
+    fn try_from(source: &str) -> ValueResult<'_> {
+        Value::parse_prefix(source).map(|(value, _)| value)
+    }
+}
+
+/// Parse RESP bytes, which must be valid UTF-8 up to the end of the value.
+///
+/// Should the parsing stop on invalid UTF-8, the offending byte is reported
+/// as an [`TError::Unexpected`] error, or as an [`TError::Incomplete`] one
+/// when the bytes end in the middle of a UTF-8 sequence.
+///
+/// ```rust
+/// use squall_dot_io_resp::{Error, Node, Value};
+///
+/// assert_eq!(Value::try_from(&b"+OK\r\n"[..]), Ok(Value::SimpleString("OK".into())));
+/// assert_eq!(
+///     Value::try_from(&b"+O\xffK\r\n"[..]),
+///     Err(Error::of_unexpected(Node::SIMPLE_STRING, 2, 0xff))
+/// );
+/// ```
+impl TryFrom<&[u8]> for Value {
+    type Error = TError;
+
+    fn try_from(source: &[u8]) -> Result<Self, TError> {
+        Value::parse_prefix_bytes(source).map(|(value, _)| value)
+    }
+}
+
+/// Parse an owned RESP buffer, as `TryFrom<&[u8]>` does, dropping it
+/// afterward.
+///
+/// Owned [`String`]s are parsed by [`Value::parse_owned`]: beware that
+/// `Value::try_from(String)` doesn't parse anything, as it goes through the
+/// `From<String>` implementation.
+impl TryFrom<Vec<u8>> for Value {
+    type Error = TError;
+
+    fn try_from(source: Vec<u8>) -> Result<Self, TError> {
+        Value::try_from(&source[..])
+    }
+}
+
+/// Delegate to `TryFrom<&str>`, so that [`str::parse`] is available.
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// assert_eq!("+hi\r\n".parse::<Value>(), Ok(Value::SimpleString("hi".into())));
+/// ```
+impl FromStr for Value {
+    type Err = TError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Value::try_from(source)
+    }
+}
+
+/// Write the RESP wire form of the value, as [`Value::to_resp_bytes`] gives it.
+///
+/// [`Value::Nil`] is always written as the RESP2 null bulk string, `$-1\r\n`:
+/// use [`Value::to_resp_bytes_with`] and [`EncodeOptions`] for the RESP3 one.
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_resp(f, &EncodeOptions::default())
     }
 }
 
 impl Value {
-    fn internal_try_from(input: Input) -> InnerResult {
-        match input.source.chars().next() {
-            Some('*') => Value::extract_array(input),
-            Some('-') => Value::extract_error(input),
-            Some(':') => Value::extract_integer(input),
-            Some('$') => Value::extract_bulk_string(input),
-            Some('+') => Value::extract_simple_string(input),
-            _ => (Err(TError::of_unexpected(UNKNOWN, input.position)), 0),
+    /// Write the RESP wire form of this value into `f`, as tuned by `options`.
+    pub(crate) fn write_resp(
+        &self,
+        f: &mut impl Write,
+        options: &EncodeOptions,
+    ) -> core::fmt::Result {
+        match self {
+            Value::Nil if options.resp3 => write!(f, "_\r\n"),
+            Value::Nil => write!(f, "$-1\r\n"),
+            Value::Integer(integer) => write!(f, ":{integer}\r\n"),
+            Value::Error(message) => write!(f, "-{message}\r\n"),
+            Value::String(string) => write!(f, "${}\r\n{string}\r\n", string.len()),
+            Value::SimpleString(string) => write!(f, "+{string}\r\n"),
+            Value::Array(values) => {
+                write!(f, "*{}\r\n", values.len())?;
+                values
+                    .iter()
+                    .try_for_each(|value| value.write_resp(f, options))
+            }
+            Value::Map(pairs) => {
+                write!(f, "%{}\r\n", pairs.len())?;
+                pairs.iter().try_for_each(|(key, value)| {
+                    key.write_resp(f, options)?;
+                    value.write_resp(f, options)
+                })
+            }
         }
     }
+}
 
-    fn extract_array(input: Input) -> InnerResult {
-        let integer_input = Input {
-            position: input.position,
-            ..input
-        };
-        match Value::extract_integer(integer_input) {
-            (Ok(Value::Integer(len)), size) => {
-                let mut values = vec![];
-                let len = len as usize;
-                let mut offset = size;
-
-                while values.len() < len {
-                    let next_input = Input {
-                        position: input.position + offset,
-                        source: &input.source[offset..input.source.len()],
-                    };
-
-                    if "" == next_input.source {
-                        return (Err(TError::of_size(ARRAY, offset)), offset);
-                    }
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Integer(value)
+    }
+}
 
-                    match Value::internal_try_from(next_input) {
-                        (Ok(value), size) => {
-                            values.push(value);
-                            offset += size;
-                        }
-                        r#else => return r#else,
-                    }
-                }
+/// Wrap a string into a [`Value::String`], as is: this doesn't parse it, and
+/// neither does `Value::try_from(String)`, which goes through this
+/// implementation. Parse owned RESP strings with [`Value::parse_owned`].
+///
+/// Note that there is no `From<&str>`: it would clash with the parsing
+/// `TryFrom<&str>` implementation. Go through an owned [`String`] instead.
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
 
-                if len == values.len() {
-                    (Ok(Value::Array(values)), offset)
-                } else {
-                    (Err(TError::of_size(ARRAY, offset + 1)), offset + 1)
-                }
+impl From<Vec<Value>> for Value {
+    fn from(values: Vec<Value>) -> Self {
+        Value::Array(values)
+    }
+}
+
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// assert_eq!(
+///     Value::from([Value::Integer(1), Value::Integer(2)]),
+///     Value::try_from("*2\r\n:1\r\n:2\r\n").unwrap()
+/// );
+/// ```
+impl<const N: usize> From<[Value; N]> for Value {
+    fn from(values: [Value; N]) -> Self {
+        Value::Array(values.into())
+    }
+}
+
+/// Convert `None` into [`Value::Nil`], and `Some` into its wrapped value.
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// assert_eq!(Value::from(Some(5)), Value::Integer(5));
+/// assert_eq!(Value::from(None::<i64>), Value::Nil);
+/// ```
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Nil, Into::into)
+    }
+}
+
+/// Collect values into a [`Value::Array`].
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// let value: Value = (1..=3).map(Value::Integer).collect();
+///
+/// assert_eq!(value, Value::try_from("*3\r\n:1\r\n:2\r\n:3\r\n").unwrap());
+/// ```
+impl FromIterator<Value> for Value {
+    fn from_iter<I: IntoIterator<Item = Value>>(values: I) -> Self {
+        Value::Array(values.into_iter().collect())
+    }
+}
+
+/// Append values to the entries of a [`Value::Array`].
+///
+/// [`Value::Nil`], which is the [`Default`] value, is taken as an empty
+/// array. Any other value is first turned into an array, of which it is the
+/// first entry.
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// let mut value = Value::command(&["DEL"]);
+///
+/// value.extend(["a", "b"].map(|key| Value::String(key.into())));
+/// assert_eq!(value, Value::command(&["DEL", "a", "b"]));
+///
+/// let mut value = Value::default();
+///
+/// value.extend([Value::Integer(1)]);
+/// assert_eq!(value, Value::Array(vec![Value::Integer(1)]));
+/// ```
+impl Extend<Value> for Value {
+    fn extend<I: IntoIterator<Item = Value>>(&mut self, values: I) {
+        match self {
+            Value::Array(entries) => entries.extend(values),
+            Value::Nil => *self = Value::Array(values.into_iter().collect()),
+            value => {
+                let mut entries = vec![core::mem::take(value)];
+
+                entries.extend(values);
+                *value = Value::Array(entries);
             }
-            r#else => return r#else,
         }
     }
+}
+
+/// Iterate over the entries of a [`Value::Array`].
+///
+/// Values which aren't arrays yield nothing.
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// let mut sum = 0;
+///
+/// for value in Value::try_from("*2\r\n:1\r\n:2\r\n").unwrap() {
+///     sum += value.as_integer().unwrap();
+/// }
+///
+/// assert_eq!(sum, 3);
+/// ```
+impl IntoIterator for Value {
+    type Item = Value;
+    type IntoIter = vec::IntoIter<Value>;
 
-    fn extract_error(input: Input) -> InnerResult {
-        match Value::extract_simple_string(input) {
-            (Ok(Value::String(message)), size) => (Ok(Value::Error(message)), size),
-            r#else => r#else,
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Value::Array(values) => values.into_iter(),
+            _ => vec![].into_iter(),
         }
     }
+}
 
-    fn extract_integer(input: Input) -> InnerResult {
-        // TODO: Support negative numbers
-        let node = match &input.source[0..1] {
-            ":" => INTEGER,
-            _ => SIZE,
-        };
-        let position = input.position + 1;
+/// Iterate over the entries of a borrowed [`Value::Array`].
+///
+/// Values which aren't arrays yield nothing.
+impl<'a> IntoIterator for &'a Value {
+    type Item = &'a Value;
+    type IntoIter = core::slice::Iter<'a, Value>;
 
-        if let Some(i) = input.source.find("\r\n") {
-            return match input.source[1..i].parse::<i64>().ok() {
-                Some(value) => (Ok(Value::Integer(value)), i + 2),
-                _ => (Err(TError::of_type(node, position)), position),
-            };
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Value::Array(values) => values.iter(),
+            _ => [].iter(),
         }
-
-        (
-            Err(TError::of_unexpected(node, input.source.len())),
-            input.source.len(),
-        )
     }
+}
+
+/// Extract the integer of a [`Value::Integer`].
+///
+/// Any other variant is handed back untouched as the error.
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// assert_eq!(i64::try_from(Value::Integer(5)), Ok(5));
+/// assert_eq!(i64::try_from(Value::Nil), Err(Value::Nil));
+/// ```
+impl TryFrom<Value> for i64 {
+    type Error = Value;
 
-    fn extract_bulk_string(input: Input) -> InnerResult {
-        if input.source.starts_with("$-1\r\n") {
-            return (Ok(Value::Nil), 5);
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Integer(integer) => Ok(integer),
+            r#else => Err(r#else),
         }
+    }
+}
 
-        match Self::extract_integer(Input { ..input }) {
-            (Ok(Value::Integer(size)), _) => {
-                let start = 1 + size.to_string().len() + 2;
-                let end = start + size as usize;
+/// Extract the string of a [`Value::String`] or [`Value::SimpleString`].
+///
+/// Any other variant, [`Value::Error`] included, is handed back untouched as
+/// the error.
+impl TryFrom<Value> for String {
+    type Error = Value;
 
-                return if input.source[end..input.source.len()].starts_with("\r\n") {
-                    (
-                        Ok(Value::String(input.source[start..end].to_string())),
-                        end + 2,
-                    )
-                } else if end < input.source.len() {
-                    let position = input.position + end;
-                    (Err(TError::of_size(BULK_STRING, position)), position)
-                } else {
-                    let position = input.position + end + 1;
-                    (Err(TError::of_size(BULK_STRING, position)), position)
-                };
-            }
-            (Err(error), size) => (Err(error), size),
-            _ => (
-                Err(TError::of_unexpected(BULK_STRING, input.position + 1)),
-                input.position + 1,
-            ),
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(string) | Value::SimpleString(string) => Ok(string),
+            r#else => Err(r#else),
         }
     }
+}
 
-    fn extract_simple_string(input: Input) -> InnerResult {
-        let node = match &input.source[0..1] {
-            "+" => SIMPLE_STRING,
-            _ => ERROR,
-        };
-        let mut position = input.position + 1;
-
-        if let Some(i) = input.source.find("\r\n") {
-            // @formatter::off
-            match input.source.find('\r').filter(|&p| p < i)
-                .or_else(|| input.source.find('\n').filter(|&p| p < i))
-            // @formatter::on
-            {
-                Some(shift) => position = input.position + shift,
-                _ => return (Ok(Value::String(input.source[1..i].into())), i + 2),
-            }
-        }
+/// Extract the values of a [`Value::Array`].
+///
+/// Any other variant is handed back untouched as the error.
+impl TryFrom<Value> for Vec<Value> {
+    type Error = Value;
 
-        (Err(TError::of_unexpected(node, position)), position)
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Array(values) => Ok(values),
+            r#else => Err(r#else),
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Node::{ARRAY, BULK_STRING, INTEGER, SIMPLE_STRING, SIZE};
+/// Compare the string of a [`Value::String`] or [`Value::SimpleString`].
+///
+/// Any other variant, [`Value::Error`] included, is never equal.
+///
+/// ```rust
+/// use squall_dot_io_resp::Value;
+///
+/// assert_eq!(Value::try_from("$2\r\nOK\r\n").unwrap(), "OK");
+/// assert_ne!(Value::try_from("-OK\r\n").unwrap(), "OK");
+/// assert_eq!(Value::try_from(":5\r\n").unwrap(), 5);
+/// ```
+impl PartialEq<str> for Value {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
 
-    use super::super::{Error, Value};
+impl PartialEq<&str> for Value {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
 
-    #[test]
-    fn value_implement_try_from_resp_nil() {
-        assert_eq!("$-1\r\n".try_into(), Ok(Value::Nil));
+/// Compare the integer of a [`Value::Integer`].
+///
+/// Any other variant is never equal.
+impl PartialEq<i64> for Value {
+    fn eq(&self, other: &i64) -> bool {
+        self.as_integer() == Some(*other)
     }
+}
 
-    #[test]
-    fn value_implement_try_from() {
-        let _value: Result<Value, Error> = "".try_into();
+impl Value {
+    /// Parse the RESP value found at the start of `source`.
+    ///
+    /// Unlike [`Value::try_from`], the number of bytes consumed by that value
+    /// is returned alongside it, so that callers managing their own buffer
+    /// know where the next value starts. Any trailing bytes are left alone.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(
+    ///     Value::parse_prefix(":10\r\n+OK\r\n"),
+    ///     Ok((Value::Integer(10), 5))
+    /// );
+    /// ```
+    pub fn parse_prefix(source: &str) -> Result<(Value, usize), TError> {
+        Value::parse_prefix_with(source, &ParseOptions::default())
     }
 
-    #[test]
-    fn value_implement_try_from_resp_array() {
-        assert_eq!("*0\r\n".try_into(), Ok(Value::Array(vec![])));
-        assert_eq!(
-            "*5\r\n$-1\r\n:447\r\n-Oh oh!\r\n+Hourly\r\n$26\r\nSi vis pacem,\r\npara bellum\r\n"
-                .try_into(),
-            Ok(Value::Array(vec![
-                Value::Nil,
-                Value::Integer(447),
-                Value::Error("Oh oh!".into()),
-                Value::String("Hourly".into()),
-                Value::String("Si vis pacem,\r\npara bellum".into()),
-            ]))
-        );
+    /// Parse the RESP value found at the start of the owned `source`, as
+    /// `TryFrom<&str>` does, dropping it afterward.
+    ///
+    /// Beware that `Value::try_from(String)` compiles as well, but doesn't
+    /// parse anything: it goes through the `From<String>` implementation,
+    /// which wraps the string into a [`Value::String`] as is.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let source = String::from(":1\r\n");
+    ///
+    /// assert_eq!(Value::parse_owned(source.clone()), Ok(Value::Integer(1)));
+    /// assert_eq!(Value::try_from(source), Ok(Value::String(":1\r\n".into())));
+    /// ```
+    pub fn parse_owned(source: String) -> Result<Value, TError> {
+        Value::try_from(source.as_str())
     }
 
-    #[test]
-    fn value_implement_try_from_resp_nested_array() {
-        let got = "*2\r\n*3\r\n+A\r\n+B\r\n+C\r\n*3\r\n:1\r\n:2\r\n:3\r\n".try_into()
-            as Result<Value, Error>;
-        let expected = Ok(Value::Array(vec![
-            Value::Array(vec![
-                Value::String("A".into()),
-                Value::String("B".into()),
-                Value::String("C".into()),
-            ]),
-            Value::Array(vec![
-                Value::Integer(1),
-                Value::Integer(2),
-                Value::Integer(3),
-            ]),
-        ])) as Result<Value, Error>;
+    /// Parse the RESP value found at the start of `source`, building it out
+    /// of the buffers pooled in `scratch`, as [`Scratch`] details.
+    ///
+    /// Trailing bytes are ignored, as they are by [`Value::try_from`]. Should
+    /// the parsing fail, the buffers of the values read so far go back to
+    /// `scratch`.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::{Scratch, Value};
+    ///
+    /// let mut scratch = Scratch::new();
+    /// let value = Value::parse_in("*1\r\n+OK\r\n", &mut scratch).unwrap();
+    ///
+    /// assert_eq!(value, Value::Array(vec![Value::SimpleString("OK".into())]));
+    /// scratch.recycle(value);
+    /// ```
+    pub fn parse_in<'a>(source: &'a str, scratch: &mut Scratch) -> ValueResult<'a> {
+        let mut builder = Builder {
+            scratch: core::mem::take(scratch),
+            ..Builder::default()
+        };
+        let size = parser::visit(source, &mut builder);
 
-        assert_eq!(got, expected);
-    }
+        for values in core::mem::take(&mut builder.arrays) {
+            builder.scratch.recycle(Value::Array(values));
+        }
+        *scratch = builder.scratch;
 
-    #[test]
-    fn value_implement_try_from_resp_array_with_mismatching_size() {
-        assert_eq!(
-            "*2\r\n$-1\r\n".try_into() as Result<Value, Error>,
-            Err(Error::of_size(ARRAY, 9))
-        );
+        let size = size?;
+
+        builder
+            .value
+            .ok_or_else(|| TError::of_incomplete(UNKNOWN, size))
     }
 
-    #[test]
-    fn value_implement_try_from_resp_error() {
-        assert_eq!("-My bad\r\n".try_into(), Ok(Value::Error("My bad".into())));
+    /// Parse the RESP value found at the start of `source`, along with the
+    /// RESP3 attribute annotating it, if any.
+    ///
+    /// Attributes of nested values are skipped, as they are by
+    /// [`Value::try_from`].
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(
+    ///     Value::parse_attributed("|1\r\n+ttl\r\n:60\r\n+OK\r\n"),
+    ///     Ok((
+    ///         Value::SimpleString("OK".into()),
+    ///         Some(vec![(Value::SimpleString("ttl".into()), Value::Integer(60))])
+    ///     ))
+    /// );
+    /// ```
+    pub fn parse_attributed(source: &str) -> Result<(Value, Option<Attributes>), TError> {
+        let mut builder = Builder::default();
+        let size = parser::visit(source, &mut builder)?;
+
+        match builder.value {
+            Some(value) => Ok((value, builder.attributes)),
+            None => Err(TError::of_incomplete(UNKNOWN, size)),
+        }
     }
 
-    #[test]
-    fn value_implement_try_from_resp_integer() {
-        assert_eq!(":10\r\n".try_into(), Ok(Value::Integer(10i64)));
+    /// Parse the RESP value found at the start of `input`, then reslice
+    /// `input` past it, to the bytes left.
+    ///
+    /// Should the parsing fail, `input` is left untouched.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let mut input = ":10\r\n+OK\r\n";
+    ///
+    /// assert_eq!(Value::parse_advance(&mut input), Ok(Value::Integer(10)));
+    /// assert_eq!(input, "+OK\r\n");
+    /// ```
+    pub fn parse_advance(input: &mut &str) -> Result<Value, TError> {
+        let (value, size) = Value::parse_prefix(input)?;
+
+        *input = &input[size..];
+        Ok(value)
     }
 
-    #[test]
-    fn value_implement_try_from_resp_integer_with_invalid_integer() {
-        assert_eq!(
-            ":Yikes\r\n".try_into() as Result<Value, Error>,
-            Err(Error::of_type(INTEGER, 1))
-        );
-        assert_eq!(
-            ":0".try_into() as Result<Value, Error>,
-            Err(Error::of_unexpected(INTEGER, 2))
-        );
+    /// Parse the RESP value found at the start of `source` bytes, as
+    /// `TryFrom<&[u8]>` does, along with the number of bytes it spans.
+    pub(crate) fn parse_prefix_bytes(source: &[u8]) -> Result<(Value, usize), TError> {
+        parser::parse_utf8(source, Value::parse_prefix)
     }
 
-    #[test]
-    fn value_implement_try_from_resp_bulk_string() {
-        assert_eq!(
-            "$4\r\nOops\r\n".try_into(),
-            Ok(Value::String("Oops".into()))
-        );
-        assert_eq!(
-            "$7\r\nOh\r\nOh!\r\n".try_into(),
-            Ok(Value::String("Oh\r\nOh!".into()))
-        );
+    fn parse_prefix_with(source: &str, options: &ParseOptions) -> Result<(Value, usize), TError> {
+        let mut builder = Builder::default();
+        let size = parser::visit_with(source, &mut builder, options)?;
+
+        match builder.value {
+            Some(value) => Ok((value, size)),
+            None => Err(TError::of_incomplete(UNKNOWN, size)),
+        }
     }
 
-    #[test]
-    fn value_implement_try_from_resp_bulk_string_with_mismatching_len() {
-        assert_eq!(
-            "$5\r\nOops\r\n".try_into() as Result<Value, Error>,
-            Err(Error::of_size(BULK_STRING, 9))
-        );
-        assert_eq!(
-            "$3\r\nOops\r\n".try_into() as Result<Value, Error>,
-            Err(Error::of_size(BULK_STRING, 7))
-        );
+    /// Parse the RESP value found at the start of `source`, reporting its
+    /// tokens to `visitor` rather than building a [`Value`].
+    ///
+    /// The number of bytes consumed by that value is returned. Should the
+    /// parsing fail, `visitor` would have been called for the tokens read
+    /// before the error.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::{Value, Visitor};
+    ///
+    /// struct Sum(i64);
+    ///
+    /// impl Visitor<'_> for Sum {
+    ///     fn on_integer(&mut self, integer: i64) {
+    ///         self.0 += integer;
+    ///     }
+    /// }
+    ///
+    /// let mut sum = Sum(0);
+    ///
+    /// Value::parse_events("*2\r\n:1\r\n*1\r\n:2\r\n", &mut sum).unwrap();
+    /// assert_eq!(sum.0, 3);
+    /// ```
+    pub fn parse_events<'a>(
+        source: &'a str,
+        visitor: &mut impl Visitor<'a>,
+    ) -> Result<usize, TError> {
+        parser::visit(source, visitor)
     }
 
-    #[test]
-    fn value_implement_try_from_resp_simple_string() {
-        assert_eq!(
-            "+Anatomy\r\n".try_into(),
-            Ok(Value::String("Anatomy".into()))
-        );
+    /// Check that `source` starts with a well-formed RESP value, without
+    /// building it.
+    ///
+    /// Nothing gets allocated but the parser's own stack of open arrays, so
+    /// that this is cheaper than [`Value::try_from`], which reports the very
+    /// same errors.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::{Error, Node, Value};
+    ///
+    /// assert_eq!(Value::validate("*2\r\n$1\r\nA\r\n:1\r\n"), Ok(()));
+    /// assert_eq!(
+    ///     Value::validate("*2\r\n$1\r\nA\r\n"),
+    ///     Err(Error::of_incomplete(Node::ARRAY, 11))
+    /// );
+    /// ```
+    pub fn validate(source: &str) -> Result<(), TError> {
+        parser::visit(source, &mut Validator).map(|_| ())
+    }
+
+    /// Count the bytes spanned by the RESP value found at the start of
+    /// `source`, without building it, as [`Value::validate`] checks it.
+    ///
+    /// `None` is returned while that value isn't complete yet, so that
+    /// stream readers know to wait for more bytes. Malformed values are
+    /// reported as errors.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(Value::frame_len("*1\r\n$2\r\nOK\r\n:1\r\n"), Ok(Some(12)));
+    /// assert_eq!(Value::frame_len("*1\r\n$2\r\nO"), Ok(None));
+    /// ```
+    pub fn frame_len(source: &str) -> Result<Option<usize>, TError> {
+        match parser::visit(source, &mut Validator) {
+            Ok(size) => Ok(Some(size)),
+            Err(error) if error.is_recoverable() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Parse a RESP value from `source`, as tuned by `options`.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::{Error, Node, ParseOptions, Value};
+    ///
+    /// let strict = ParseOptions {
+    ///     strict: true,
+    ///     ..ParseOptions::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     Value::parse_with("+OK\r\nEXTRA", &ParseOptions::default()),
+    ///     Ok(Value::SimpleString("OK".into()))
+    /// );
+    /// assert_eq!(
+    ///     Value::parse_with("+OK\r\nEXTRA", &strict),
+    ///     Err(Error::of_unexpected(Node::UNKNOWN, 5, b'E'))
+    /// );
+    /// ```
+    pub fn parse_with<'a>(source: &'a str, options: &ParseOptions) -> ValueResult<'a> {
+        let (value, size) = Value::parse_prefix_with(source, options)?;
+
+        if options.strict && size < source.len() {
+            return Err(TError::of_unexpected(
+                UNKNOWN,
+                size,
+                source.as_bytes()[size],
+            ));
+        }
+
+        Ok(value)
+    }
+
+    /// Parse `source` as exactly one RESP value, as [`Value::parse_with`]
+    /// does with [`ParseOptions::strict`] enabled: the first leftover byte is
+    /// an [`TError::Unexpected`] one.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::{Error, Node, Value};
+    ///
+    /// assert_eq!(Value::try_from_exact(":1\r\n"), Ok(Value::Integer(1)));
+    /// assert_eq!(
+    ///     Value::try_from_exact(":1\r\n:2\r\n"),
+    ///     Err(Error::of_unexpected(Node::UNKNOWN, 4, b':'))
+    /// );
+    /// ```
+    pub fn try_from_exact(source: &str) -> ValueResult<'_> {
+        let strict = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+
+        Value::parse_with(source, &strict)
+    }
+
+    /// Parse all the RESP values found one after the other in `source`.
+    ///
+    /// The parsed values are returned with the number of bytes they span.
+    /// Parsing stops cleanly before a trailing value which isn't complete yet,
+    /// so that callers managing their own buffer can keep the bytes left for
+    /// later. Malformed values, on the other hand, are reported as errors.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(
+    ///     Value::parse_all(":1\r\n+OK\r\n$5\r\nHel"),
+    ///     Ok((vec![Value::Integer(1), Value::SimpleString("OK".into())], 9))
+    /// );
+    /// ```
+    pub fn parse_all(source: &str) -> Result<(Vec<Value>, usize), TError> {
+        let mut values = vec![];
+        let mut offset = 0;
+
+        while offset < source.len() {
+            match Value::parse_prefix(&source[offset..]) {
+                Ok((value, size)) => {
+                    values.push(value);
+                    offset += size;
+                }
+                Err(error) if error.is_recoverable() => break,
+                Err(error) => return Err(error.with_offset(offset)),
+            }
+        }
+
+        Ok((values, offset))
+    }
+
+    /// Parse a Redis inline command, as typed in a telnet session: its
+    /// whitespace-separated words, up to the `\r\n` sequence, are gathered
+    /// into a [`Value::Array`] of bulk strings, as [`Value::command`] does.
+    ///
+    /// Quotes aren't interpreted, and trailing bytes are ignored.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(
+    ///     Value::parse_inline("SET key  val\r\n"),
+    ///     Ok(Value::command(&["SET", "key", "val"]))
+    /// );
+    /// ```
+    pub fn parse_inline(source: &str) -> ValueResult<'_> {
+        let line = match source.find('\n') {
+            Some(i) if source[..i].ends_with('\r') => &source[..i - 1],
+            Some(i) => return Err(TError::of_unexpected(UNKNOWN, i, b'\n')),
+            None => return Err(TError::of_incomplete(UNKNOWN, source.len())),
+        };
+
+        if let Some(i) = line.find('\r') {
+            return Err(TError::of_unexpected(UNKNOWN, i, b'\r'));
+        }
+
+        Ok(Value::command(&line.split_whitespace().collect::<Vec<_>>()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Value {
+    /// Read a single RESP value from `reader`.
+    ///
+    /// Lines are read one byte at a time, and bulk string payloads at once,
+    /// so that nothing past that value is consumed: consider wrapping
+    /// unbuffered readers (sockets, files) into a [`std::io::BufReader`].
+    /// The bytes read are only parsed once the value is complete, or
+    /// malformed.
+    ///
+    /// Malformed RESP, invalid UTF-8 included, is reported as an
+    /// [`ErrorKind::InvalidData`] error wrapping the parsing [`TError`], and
+    /// `reader` running dry before the value is complete as an
+    /// [`ErrorKind::UnexpectedEof`] one.
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let mut reader = Cursor::new(b":1\r\n:2\r\n");
+    ///
+    /// assert_eq!(Value::from_reader(&mut reader).unwrap(), Value::Integer(1));
+    /// assert_eq!(Value::from_reader(&mut reader).unwrap(), Value::Integer(2));
+    /// ```
+    pub fn from_reader<R: Read>(reader: &mut R) -> std::io::Result<Value> {
+        let mut buffer = vec![];
+        let mut framer = Framer::default();
+        let mut next = Next::Line;
+        let mut byte = [0u8];
+
+        loop {
+            let start = buffer.len();
+
+            next = match next {
+                Next::Line => {
+                    while b'\n' != byte[0] {
+                        reader.read_exact(&mut byte)?;
+                        buffer.push(byte[0]);
+                    }
+                    byte[0] = 0;
+                    framer.on_line(&buffer[start..])
+                }
+                Next::Payload(size) => {
+                    if size != reader.take(size).read_to_end(&mut buffer)? as u64 {
+                        return Err(ErrorKind::UnexpectedEof.into());
+                    }
+                    match buffer.ends_with(b"\r\n") {
+                        true => framer.on_payload(),
+                        false => Next::Parse,
+                    }
+                }
+                // Should the value be incomplete after all, it's parsed
+                // again after every line.
+                Next::Parse => match Value::parse_prefix_bytes(&buffer) {
+                    Ok((value, _)) => return Ok(value),
+                    Err(error) if error.is_recoverable() => Next::Line,
+                    Err(error) => return Err(error.into_io_error()),
+                },
+            };
+        }
+    }
+}
+
+impl Value {
+    /// Build a Redis-style command: an array of bulk strings.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(
+    ///     Value::command(&["SET", "key", "val"]).to_resp_bytes(),
+    ///     b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$3\r\nval\r\n"
+    /// );
+    /// ```
+    pub fn command(args: &[&str]) -> Value {
+        Value::Array(args.iter().map(|&arg| Value::String(arg.into())).collect())
+    }
+
+    /// Build a [`Value::SimpleString`], which can't hold any `\r` nor `\n`.
+    ///
+    /// The first one found is an [`TError::Unexpected`] byte, indexed in
+    /// `string`: a [`Value::String`] can hold it instead.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::{Error, Node::SIMPLE_STRING, Value};
+    ///
+    /// assert_eq!(Value::simple_string("OK"), Ok(Value::SimpleString("OK".into())));
+    /// assert_eq!(
+    ///     Value::simple_string("O\nK"),
+    ///     Err(Error::of_unexpected(SIMPLE_STRING, 1, b'\n'))
+    /// );
+    /// ```
+    pub fn simple_string(string: &str) -> Result<Value, TError> {
+        match string
+            .bytes()
+            .position(|byte| byte == b'\r' || byte == b'\n')
+        {
+            Some(index) => Err(TError::of_unexpected(
+                SIMPLE_STRING,
+                index,
+                string.as_bytes()[index],
+            )),
+            None => Ok(Value::SimpleString(string.into())),
+        }
+    }
+
+    /// Get the integer of a [`Value::Integer`], `None` otherwise.
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(integer) => Some(*integer),
+            _ => None,
+        }
+    }
+
+    /// Get the integer of a [`Value::Integer`] as a float, `None` otherwise.
+    ///
+    /// Integers beyond 2^53 in magnitude are rounded to the nearest
+    /// float, as `as` casts do. There is no RESP3 double variant to read yet.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(Value::try_from(":-3\r\n").unwrap().as_f64(), Some(-3.0));
+    /// assert_eq!(Value::String("3".into()).as_f64(), None);
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_integer().map(|integer| integer as f64)
+    }
+
+    /// Get the boolean of a [`Value::Integer`] that is `0` or `1`, as Redis
+    /// commands reply, `None` otherwise.
+    ///
+    /// [`Value::Nil`] is `None` as well, rather than `false`: it tells a
+    /// missing reply apart from a negative one.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(Value::try_from(":1\r\n").unwrap().as_bool(), Some(true));
+    /// assert_eq!(Value::Nil.as_bool(), None);
+    /// ```
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Integer(0) => Some(false),
+            Value::Integer(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Borrow the string of a [`Value::String`] or [`Value::SimpleString`],
+    /// `None` otherwise.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) | Value::SimpleString(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Borrow the message of a [`Value::Error`], `None` otherwise.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// if let Some(message) = Value::try_from("-ERR oops\r\n").unwrap().as_error() {
+    ///     assert_eq!(message, "ERR oops");
+    /// }
+    /// ```
+    pub fn as_error(&self) -> Option<&str> {
+        match self {
+            Value::Error(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Borrow the code of a [`Value::Error`], `None` otherwise.
+    ///
+    /// Following Redis conventions, that code is the first word of the
+    /// message, when it's made of uppercase letters only: e.g. `WRONGTYPE`
+    /// in `-WRONGTYPE Operation against a key...\r\n`. Messages without such
+    /// a word have no code.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("-WRONGTYPE Operation against a key\r\n").unwrap();
+    ///
+    /// assert_eq!(value.error_code(), Some("WRONGTYPE"));
+    /// assert_eq!(Value::Error("Oh oh!".into()).error_code(), None);
+    /// ```
+    pub fn error_code(&self) -> Option<&str> {
+        match self {
+            Value::Error(message) => message
+                .split(' ')
+                .next()
+                .filter(|code| !code.is_empty())
+                .filter(|code| code.bytes().all(|byte| byte.is_ascii_uppercase())),
+            _ => None,
+        }
+    }
+
+    /// Take the string of a [`Value::String`] or [`Value::SimpleString`].
+    ///
+    /// Any other variant is handed back untouched as the error, as
+    /// `TryFrom<Value>` for [`String`] does.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(Value::String("OK".into()).into_string(), Ok("OK".into()));
+    /// assert_eq!(Value::Integer(1).into_string(), Err(Value::Integer(1)));
+    /// ```
+    pub fn into_string(self) -> Result<String, Value> {
+        String::try_from(self)
+    }
+
+    /// Borrow the values of a [`Value::Array`], `None` otherwise.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*2\r\n:1\r\n:2\r\n").unwrap();
+    ///
+    /// assert_eq!(
+    ///     value.as_array().and_then(|a| a.first()).and_then(Value::as_integer),
+    ///     Some(1)
+    /// );
+    /// ```
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the key/value pairs of a [`Value::Map`], `None` otherwise.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("%1\r\n+a\r\n:1\r\n").unwrap();
+    ///
+    /// for (key, value) in value.iter_map().unwrap() {
+    ///     assert_eq!((key, value), (&Value::SimpleString("a".into()), &Value::Integer(1)));
+    /// }
+    /// ```
+    pub fn iter_map(&self) -> Option<impl Iterator<Item = (&Value, &Value)>> {
+        match self {
+            Value::Map(pairs) => Some(pairs.iter().map(|(key, value)| (key, value))),
+            _ => None,
+        }
+    }
+
+    /// Read the reply to the `HELLO` handshake: a [`Value::Map`] of server
+    /// details with RESP3, or an even-length [`Value::Array`] of its keys
+    /// and values in turn with RESP2. `None` otherwise.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let reply = Value::try_from("%2\r\n$6\r\nserver\r\n$5\r\nredis\r\n$5\r\nproto\r\n:3\r\n").unwrap();
+    /// let hello = reply.as_hello().unwrap();
+    ///
+    /// assert_eq!(hello.server.as_deref(), Some("redis"));
+    /// assert_eq!(hello.proto, Some(3));
+    /// ```
+    pub fn as_hello(&self) -> Option<HelloInfo> {
+        match self {
+            Value::Map(pairs) => Some(HelloInfo::from_pairs(
+                pairs.iter().map(|(key, value)| (key, value)),
+            )),
+            Value::Array(values) if 0 == values.len() % 2 => Some(HelloInfo::from_pairs(
+                values.chunks_exact(2).map(|pair| (&pair[0], &pair[1])),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Whether this is [`Value::Nil`].
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Value::Nil)
+    }
+
+    /// Name the variant of this value, for logs and error messages.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(Value::Integer(1).type_name(), "integer");
+    /// assert_eq!(Value::SimpleString("OK".into()).type_name(), "simple_string");
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Nil => "nil",
+            Value::Integer(_) => "integer",
+            Value::Error(_) => "error",
+            Value::String(_) => "string",
+            Value::SimpleString(_) => "simple_string",
+            Value::Array(_) => "array",
+            Value::Map(_) => "map",
+        }
+    }
+
+    /// Get the entries count of a [`Value::Array`], or the key/value pairs
+    /// count of a [`Value::Map`], `None` otherwise.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Value::Array(values) => Some(values.len()),
+            Value::Map(pairs) => Some(pairs.len()),
+            _ => None,
+        }
+    }
+
+    /// Borrow the entry at `index` of a [`Value::Array`].
+    ///
+    /// `None` is returned for out of bounds indexes and for values which
+    /// aren't arrays.
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        self.as_array().and_then(|values| values.get(index))
+    }
+
+    /// Whether this is a [`Value::Array`] with an entry equal to `needle`.
+    ///
+    /// Nested arrays aren't searched, and values which aren't arrays contain
+    /// nothing.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::command(&["SET", "key", "val"]);
+    ///
+    /// assert!(value.contains(&Value::String("key".into())));
+    /// assert!(!value.contains(&Value::SimpleString("key".into())));
+    /// ```
+    pub fn contains(&self, needle: &Value) -> bool {
+        self.as_array()
+            .is_some_and(|values| values.contains(needle))
+    }
+
+    /// Borrow the first entry of a [`Value::Array`].
+    ///
+    /// `None` is returned for empty arrays and for values which aren't
+    /// arrays.
+    pub fn first(&self) -> Option<&Value> {
+        self.as_array().and_then(<[Value]>::first)
+    }
+
+    /// Borrow the last entry of a [`Value::Array`].
+    ///
+    /// `None` is returned for empty arrays and for values which aren't
+    /// arrays.
+    pub fn last(&self) -> Option<&Value> {
+        self.as_array().and_then(<[Value]>::last)
+    }
+
+    /// Borrow a nested entry, following successive array indexes.
+    ///
+    /// `None` is returned as soon as a step isn't an array, or is out of
+    /// bounds. An empty `path` gives this value back.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*1\r\n*3\r\n:1\r\n:2\r\n:3\r\n").unwrap();
+    ///
+    /// assert_eq!(value.try_path(&[0, 2]), Some(&Value::Integer(3)));
+    /// ```
+    pub fn try_path(&self, path: &[usize]) -> Option<&Value> {
+        path.iter().try_fold(self, |value, &index| value.get(index))
+    }
+
+    /// Pair up the consecutive entries of a [`Value::Array`], as found in
+    /// `HGETALL`-like flat replies: `[field1, value1, field2, value2]`.
+    ///
+    /// The pairs of a [`Value::Map`] are given as they are. Other values, and
+    /// arrays with an odd entries count, are handed back untouched as the
+    /// error, as `TryFrom<Value>` conversions do.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*4\r\n+a\r\n:1\r\n+b\r\n:2\r\n").unwrap();
+    ///
+    /// assert_eq!(
+    ///     value.into_map(),
+    ///     Ok(vec![
+    ///         (Value::SimpleString("a".into()), Value::Integer(1)),
+    ///         (Value::SimpleString("b".into()), Value::Integer(2)),
+    ///     ])
+    /// );
+    /// ```
+    pub fn into_map(self) -> Result<Vec<(Value, Value)>, Value> {
+        match self {
+            Value::Array(values) if 0 == values.len() % 2 => Ok(pairs(values)),
+            Value::Map(pairs) => Ok(pairs),
+            r#else => Err(r#else),
+        }
+    }
+
+    /// Take the two entries of a [`Value::Array`] of exactly that length,
+    /// e.g. a `[key, value]` reply.
+    ///
+    /// Any other value is handed back untouched as the error.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*2\r\n+key\r\n:1\r\n").unwrap();
+    ///
+    /// assert_eq!(
+    ///     value.try_into_pair(),
+    ///     Ok((Value::SimpleString("key".into()), Value::Integer(1)))
+    /// );
+    /// ```
+    pub fn try_into_pair(self) -> Result<(Value, Value), Value> {
+        match self {
+            Value::Array(values) => match <[Value; 2]>::try_from(values) {
+                Ok([key, value]) => Ok((key, value)),
+                Err(values) => Err(Value::Array(values)),
+            },
+            r#else => Err(r#else),
+        }
+    }
+
+    /// Keep only the entries of a [`Value::Array`] for which `f` holds, in
+    /// place. Values which aren't arrays are left untouched.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let mut value = Value::try_from("*3\r\n:1\r\n$-1\r\n:2\r\n").unwrap();
+    ///
+    /// value.retain_array(|value| !value.is_nil());
+    /// assert_eq!(value, Value::try_from("*2\r\n:1\r\n:2\r\n").unwrap());
+    /// ```
+    pub fn retain_array(&mut self, f: impl FnMut(&Value) -> bool) {
+        if let Value::Array(values) = self {
+            values.retain(f);
+        }
+    }
+
+    /// Sort the entries of a [`Value::Array`] in place, as ordered by `f`.
+    /// Values which aren't arrays are left untouched.
+    ///
+    /// The sort is stable: equal entries keep their order.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let mut value = Value::command(&["b", "cc", "a"]);
+    ///
+    /// value.sort_array_by(|a, b| a.as_str().map(str::len).cmp(&b.as_str().map(str::len)));
+    /// assert_eq!(value, Value::command(&["b", "a", "cc"]));
+    /// ```
+    pub fn sort_array_by(&mut self, f: impl FnMut(&Value, &Value) -> Ordering) {
+        if let Value::Array(values) = self {
+            values.sort_by(f);
+        }
+    }
+
+    /// Sort the entries of a [`Value::Array`] in place, in the order
+    /// [`Value`] documents. Values which aren't arrays are left untouched.
+    pub fn sort_array(&mut self) {
+        self.sort_array_by(Value::cmp)
+    }
+
+    /// Splice the entries of the arrays nested in a [`Value::Array`] into it.
+    ///
+    /// Only a single level is flattened: arrays nested deeper are kept as
+    /// they are, and so are the entries which aren't arrays. Values which
+    /// aren't arrays are given back untouched.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*2\r\n*2\r\n:1\r\n:2\r\n*1\r\n:3\r\n").unwrap();
+    ///
+    /// assert_eq!(value.flatten(), Value::try_from("*3\r\n:1\r\n:2\r\n:3\r\n").unwrap());
+    /// ```
+    pub fn flatten(self) -> Value {
+        match self {
+            Value::Array(values) => Value::Array(
+                values
+                    .into_iter()
+                    .flat_map(|value| match value {
+                        Value::Array(values) => values,
+                        value => vec![value],
+                    })
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    /// Apply `f` to the string of every [`Value::String`] and
+    /// [`Value::SimpleString`], this value and its descendants included.
+    ///
+    /// Other values, [`Value::Error`] included, are kept as they are.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::command(&["get", "key"]);
+    ///
+    /// assert_eq!(
+    ///     value.map_strings(&mut |string| string.to_uppercase()),
+    ///     Value::command(&["GET", "KEY"])
+    /// );
+    /// ```
+    pub fn map_strings(self, f: &mut impl FnMut(String) -> String) -> Value {
+        match self {
+            Value::String(string) => Value::String(f(string)),
+            Value::SimpleString(string) => Value::SimpleString(f(string)),
+            Value::Array(values) => Value::Array(
+                values
+                    .into_iter()
+                    .map(|value| value.map_strings(f))
+                    .collect(),
+            ),
+            Value::Map(pairs) => Value::Map(
+                pairs
+                    .into_iter()
+                    .map(|(key, value)| (key.map_strings(f), value.map_strings(f)))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    /// Release the spare capacity of the strings and vectors held by this
+    /// value, and by its descendants.
+    ///
+    /// Parsed values only hold what was read, so that this mostly matters for
+    /// values built or edited in place. Nothing else is changed: simple and
+    /// bulk strings are kept apart, as the encoder writes them back as such.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let mut value = Value::Array(Vec::with_capacity(16));
+    ///
+    /// value.normalize();
+    ///
+    /// if let Value::Array(values) = value {
+    ///     assert_eq!(values.capacity(), 0);
+    /// }
+    /// ```
+    pub fn normalize(&mut self) {
+        let mut values = vec![self];
+
+        while let Some(value) = values.pop() {
+            match value {
+                Value::Error(string) | Value::String(string) | Value::SimpleString(string) => {
+                    string.shrink_to_fit()
+                }
+                Value::Array(entries) => {
+                    entries.shrink_to_fit();
+                    values.extend(entries.iter_mut());
+                }
+                Value::Map(pairs) => {
+                    pairs.shrink_to_fit();
+                    values.extend(pairs.iter_mut().flat_map(|(key, value)| [key, value]));
+                }
+                Value::Nil | Value::Integer(_) => {}
+            }
+        }
+    }
+
+    /// Whether this is a [`Value::Array`] or a [`Value::Map`] without
+    /// entries.
+    ///
+    /// Values which aren't arrays nor maps are never empty.
+    pub fn is_empty(&self) -> bool {
+        Some(0) == self.len()
+    }
+
+    /// Whether this value equals `other`, regardless of the order of
+    /// [`Value::Array`] entries, e.g. to compare replies of Redis sets.
+    ///
+    /// Arrays are compared as multisets: each entry must show up as many
+    /// times in both of them. Entries themselves, as well as values which
+    /// aren't arrays, are compared with `==`.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*3\r\n:1\r\n:2\r\n:1\r\n").unwrap();
+    ///
+    /// assert!(value.eq_unordered(&Value::try_from("*3\r\n:2\r\n:1\r\n:1\r\n").unwrap()));
+    /// assert!(!value.eq_unordered(&Value::try_from("*3\r\n:2\r\n:2\r\n:1\r\n").unwrap()));
+    /// ```
+    pub fn eq_unordered(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Array(values), Value::Array(others)) if values.len() == others.len() => {
+                let mut values: Vec<&Value> = values.iter().collect();
+                let mut others: Vec<&Value> = others.iter().collect();
+
+                values.sort_unstable();
+                others.sort_unstable();
+                values == others
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Call `f` on this value, then on each of its descendants, depth-first.
+    ///
+    /// The descendants of a [`Value::Map`] are its keys and values, in turn.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*2\r\n-Oh oh!\r\n*1\r\n-Yikes\r\n").unwrap();
+    /// let mut messages = vec![];
+    ///
+    /// value.walk(&mut |value| {
+    ///     if let Value::Error(message) = value {
+    ///         messages.push(message.clone());
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(messages, vec!["Oh oh!", "Yikes"]);
+    /// ```
+    pub fn walk(&self, f: &mut impl FnMut(&Value)) {
+        let mut values = vec![self];
+
+        while let Some(value) = values.pop() {
+            f(value);
+            values.extend(value.children().rev());
+        }
+    }
+
+    /// Measure how deeply nested this value is.
+    ///
+    /// Scalars (empty arrays and maps included) are 1 deep, while arrays and
+    /// maps are 1 deeper than their deepest entry, key or value.
+    pub fn depth(&self) -> usize {
+        let mut values = vec![(self, 1)];
+        let mut depth = 0;
+
+        while let Some((value, level)) = values.pop() {
+            depth = depth.max(level);
+            values.extend(value.children().map(|value| (value, level + 1)));
+        }
+
+        depth
+    }
+
+    /// Count this value and its descendants, as [`Value::walk`] visits them.
+    ///
+    /// Scalars count as 1 node, while arrays count as 1 node plus the nodes of
+    /// their entries, and maps plus the nodes of their keys and values.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*2\r\n:1\r\n*1\r\n:2\r\n").unwrap();
+    ///
+    /// assert_eq!(value.count_nodes(), 4);
+    /// ```
+    pub fn count_nodes(&self) -> usize {
+        let mut count = 0;
+
+        self.walk(&mut |_| count += 1);
+        count
+    }
+
+    /// Render this value as an indented tree, for humans to read.
+    ///
+    /// Arrays and maps show their entries count, with their entries (keys and
+    /// values in turn, for maps) indented beneath. Scalars are shown as their
+    /// [`Debug`] output.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*2\r\n:1\r\n*1\r\n$1\r\nA\r\n").unwrap();
+    ///
+    /// assert_eq!(value.pretty(), "Array(2)\n  Integer(1)\n  Array(1)\n    String(\"A\")");
+    /// ```
+    pub fn pretty(&self) -> String {
+        let mut pretty = String::new();
+        let mut values = vec![(self, 0)];
+
+        while let Some((value, level)) = values.pop() {
+            if !pretty.is_empty() {
+                pretty.push('\n');
+            }
+            for _ in 0..level {
+                pretty.push_str("  ");
+            }
+
+            // Writing into a `String` can't fail.
+            let _ = match value {
+                Value::Array(entries) => write!(pretty, "Array({})", entries.len()),
+                Value::Map(pairs) => write!(pretty, "Map({})", pairs.len()),
+                scalar => write!(pretty, "{scalar:?}"),
+            };
+            values.extend(value.children().rev().map(|value| (value, level + 1)));
+        }
+
+        pretty
+    }
+
+    /// Iterate over the entries of a [`Value::Array`], or the keys and values
+    /// of a [`Value::Map`], in turn.
+    fn children(&self) -> impl DoubleEndedIterator<Item = &Value> {
+        let (values, pairs): (&[Value], &[(Value, Value)]) = match self {
+            Value::Array(values) => (values, &[]),
+            Value::Map(pairs) => (&[], pairs),
+            _ => (&[], &[]),
+        };
+
+        values
+            .iter()
+            .chain(pairs.iter().flat_map(|(key, value)| [key, value]))
+    }
+
+    /// Estimate the bytes taken by this value: its own size, plus the heap
+    /// bytes of its strings and vectors, and of its descendants'.
+    ///
+    /// Heap bytes are counted from capacities rather than lengths, so that
+    /// spare room is accounted for, but this is still an estimate: allocator
+    /// overheads are left out.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::try_from("*1\r\n+OK\r\n").unwrap();
+    ///
+    /// assert!(2 * size_of::<Value>() + 2 <= value.estimate_memory());
+    /// ```
+    pub fn estimate_memory(&self) -> usize {
+        let mut memory = size_of::<Value>();
+
+        // Entries are inlined in the heap bytes of the vector holding them.
+        self.walk(&mut |value| {
+            memory += match value {
+                Value::Nil | Value::Integer(_) => 0,
+                Value::Error(string) | Value::String(string) | Value::SimpleString(string) => {
+                    string.capacity()
+                }
+                Value::Array(values) => values.capacity() * size_of::<Value>(),
+                Value::Map(pairs) => pairs.capacity() * size_of::<(Value, Value)>(),
+            }
+        });
+
+        memory
+    }
+
+    /// Serialize this value into its RESP wire form.
+    ///
+    /// This is the same as the [`Display`] output, as bytes.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(
+    ///     Value::Array(vec![Value::Integer(1), Value::String("A".into())]).to_resp_bytes(),
+    ///     b"*2\r\n:1\r\n$1\r\nA\r\n"
+    /// );
+    /// ```
+    pub fn to_resp_bytes(&self) -> Vec<u8> {
+        self.to_resp_bytes_with(&EncodeOptions::default())
+    }
+
+    /// Serialize this value into its RESP wire form, as tuned by `options`.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::{EncodeOptions, Value};
+    ///
+    /// let resp3 = EncodeOptions { resp3: true };
+    ///
+    /// assert_eq!(
+    ///     Value::Array(vec![Value::Nil]).to_resp_bytes_with(&resp3),
+    ///     b"*1\r\n_\r\n"
+    /// );
+    /// ```
+    pub fn to_resp_bytes_with(&self, options: &EncodeOptions) -> Vec<u8> {
+        let mut resp = String::with_capacity(self.encoded_len_with(options));
+
+        // Writing into a `String` can't fail.
+        let _ = self.write_resp(&mut resp, options);
+        resp.into_bytes()
+    }
+
+    /// Count the bytes of this value RESP wire form, as given by
+    /// [`Value::to_resp_bytes`], without serializing it.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// let value = Value::Array(vec![Value::Integer(-1), Value::String("Hourly".into())]);
+    ///
+    /// assert_eq!(value.encoded_len(), 21);
+    /// assert_eq!(value.encoded_len(), value.to_resp_bytes().len());
+    /// ```
+    pub fn encoded_len(&self) -> usize {
+        self.encoded_len_with(&EncodeOptions::default())
+    }
+
+    /// Count the bytes of this value RESP wire form, as given by
+    /// [`Value::to_resp_bytes_with`] with the same `options`.
+    pub fn encoded_len_with(&self, options: &EncodeOptions) -> usize {
+        let mut len = 0;
+
+        // Arrays and maps only count their header: their entries are walked
+        // as well.
+        self.walk(&mut |value| {
+            len += match value {
+                Value::Nil if options.resp3 => 3,
+                Value::Nil => 5,
+                Value::Integer(integer) => 3 + decimal_len(*integer),
+                Value::Error(message) => 3 + message.len(),
+                Value::String(string) => 5 + decimal_len(string.len() as i64) + string.len(),
+                Value::SimpleString(string) => 3 + string.len(),
+                Value::Array(values) => 3 + decimal_len(values.len() as i64),
+                Value::Map(pairs) => 3 + decimal_len(pairs.len() as i64),
+            }
+        });
+
+        len
+    }
+
+    /// Parse the RESP value found at the start of `source`, then serialize it
+    /// back into its canonical wire form, as [`Value::to_resp_bytes`] does.
+    ///
+    /// Simple strings are kept apart from bulk strings, so that well-formed
+    /// values come back unchanged. Other spellings are normalized: e.g. signed
+    /// integers lose their `+` sign, null arrays become null bulk strings, and
+    /// RESP3 streamed strings and arrays get their size. Trailing bytes are
+    /// dropped.
+    ///
+    /// ```rust
+    /// use squall_dot_io_resp::Value;
+    ///
+    /// assert_eq!(Value::reencode("+OK\r\n"), Ok("+OK\r\n".into()));
+    /// assert_eq!(Value::reencode(":+5\r\n"), Ok(":5\r\n".into()));
+    /// ```
+    pub fn reencode(source: &str) -> Result<String, TError> {
+        Value::try_from(source).map(|value| value.to_string())
+    }
+}
+
+/// Count the characters of `integer` written in base 10, sign included.
+fn decimal_len(integer: i64) -> usize {
+    let digits = integer.unsigned_abs().checked_ilog10().unwrap_or(0) as usize + 1;
+
+    digits + usize::from(integer < 0)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use std::io::{Cursor, ErrorKind};
+
+    use crate::Node::{
+        ARRAY, ATTRIBUTE, BULK_STRING, ERROR, INTEGER, MAP, NIL, SIMPLE_STRING, SIZE, UNKNOWN,
+    };
+
+    use super::super::{EncodeOptions, Error, HelloInfo, ParseOptions, Value, Visitor};
+
+    #[test]
+    fn value_implement_try_from_resp_nil() {
+        assert_eq!("$-1\r\n".try_into(), Ok(Value::Nil));
+    }
+
+    #[test]
+    fn value_implement_try_from() {
+        let _value: Result<Value, Error> = "".try_into();
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_array() {
+        assert_eq!("*0\r\n".try_into(), Ok(Value::Array(vec![])));
+        assert_eq!(
+            "*5\r\n$-1\r\n:447\r\n-Oh oh!\r\n+Hourly\r\n$26\r\nSi vis pacem,\r\npara bellum\r\n"
+                .try_into(),
+            Ok(Value::Array(vec![
+                Value::Nil,
+                Value::Integer(447),
+                Value::Error("Oh oh!".into()),
+                Value::SimpleString("Hourly".into()),
+                Value::String("Si vis pacem,\r\npara bellum".into()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_nested_array() {
+        let got = "*2\r\n*3\r\n+A\r\n+B\r\n+C\r\n*3\r\n:1\r\n:2\r\n:3\r\n".try_into()
+            as Result<Value, Error>;
+        let expected = Ok(Value::Array(vec![
+            Value::Array(vec![
+                Value::SimpleString("A".into()),
+                Value::SimpleString("B".into()),
+                Value::SimpleString("C".into()),
+            ]),
+            Value::Array(vec![
+                Value::Integer(1),
+                Value::Integer(2),
+                Value::Integer(3),
+            ]),
+        ])) as Result<Value, Error>;
+
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_array_with_mismatching_size() {
+        assert_eq!(
+            "*2\r\n$-1\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_incomplete(ARRAY, 9))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_error() {
+        assert_eq!("-My bad\r\n".try_into(), Ok(Value::Error("My bad".into())));
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_integer() {
+        assert_eq!(":10\r\n".try_into(), Ok(Value::Integer(10i64)));
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_integer_with_invalid_integer() {
+        assert_eq!(
+            ":Yikes\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_type(INTEGER, 1))
+        );
+        assert_eq!(
+            ":0".try_into() as Result<Value, Error>,
+            Err(Error::of_incomplete(INTEGER, 2))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_bulk_string() {
+        assert_eq!(
+            "$4\r\nOops\r\n".try_into(),
+            Ok(Value::String("Oops".into()))
+        );
+        assert_eq!(
+            "$7\r\nOh\r\nOh!\r\n".try_into(),
+            Ok(Value::String("Oh\r\nOh!".into()))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_bulk_string_with_mismatching_len() {
+        assert_eq!(
+            "$5\r\nOops\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_size(BULK_STRING, 9))
+        );
+        assert_eq!(
+            "$3\r\nOops\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_size(BULK_STRING, 7))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_simple_string() {
+        assert_eq!(
+            "+Anatomy\r\n".try_into(),
+            Ok(Value::SimpleString("Anatomy".into()))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_simple_string_with_line_feed_or_carriage_return_in_value() {
+        assert_eq!(
+            "+Top\nBottom\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_unexpected(SIMPLE_STRING, 4, b'\n'))
+        );
+        assert_eq!(
+            "+Top\rBottom\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_unexpected(SIMPLE_STRING, 4, b'\r'))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_with_invalid_size_type() {
+        assert_eq!(
+            "*!\r\n$-1\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_type(SIZE, 1))
+        );
+        assert_eq!(
+            "$!\r\n$-1\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_type(SIZE, 1))
+        );
+    }
+
+    #[test]
+    fn value_parse_prefix_report_consumed_bytes() {
+        assert_eq!(Value::parse_prefix("$-1\r\n"), Ok((Value::Nil, 5)));
+        assert_eq!(
+            Value::parse_prefix(":447\r\n"),
+            Ok((Value::Integer(447), 6))
+        );
+        assert_eq!(
+            Value::parse_prefix("-Oh oh!\r\n"),
+            Ok((Value::Error("Oh oh!".into()), 9))
+        );
+        assert_eq!(
+            Value::parse_prefix("+Hourly\r\n"),
+            Ok((Value::SimpleString("Hourly".into()), 9))
+        );
+        assert_eq!(
+            Value::parse_prefix("$7\r\nOh\r\nOh!\r\n"),
+            Ok((Value::String("Oh\r\nOh!".into()), 13))
+        );
+        assert_eq!(
+            Value::parse_prefix("*2\r\n:1\r\n*1\r\n+A\r\n"),
+            Ok((
+                Value::Array(vec![
+                    Value::Integer(1),
+                    Value::Array(vec![Value::SimpleString("A".into())]),
+                ]),
+                16
+            ))
+        );
+    }
+
+    #[test]
+    fn value_parse_prefix_leave_trailing_bytes() {
+        let source = ":1\r\n+OK\r\n";
+        let (first, size) = Value::parse_prefix(source).unwrap();
+
+        assert_eq!(first, Value::Integer(1));
+        assert_eq!(
+            Value::parse_prefix(&source[size..]),
+            Ok((Value::SimpleString("OK".into()), 5))
+        );
+        assert_eq!(
+            Value::parse_prefix(":Yikes\r\n"),
+            Err(Error::of_type(INTEGER, 1))
+        );
+    }
+
+    #[test]
+    fn value_parse_with_strict_reject_trailing_bytes() {
+        let strict = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+
+        assert_eq!(
+            Value::parse_with("+OK\r\n", &strict),
+            Ok(Value::SimpleString("OK".into()))
+        );
+        assert_eq!(
+            Value::parse_with("+OK\r\nEXTRA", &strict),
+            Err(Error::of_unexpected(UNKNOWN, 5, b'E'))
+        );
+        assert_eq!(
+            Value::parse_with("*1\r\n:1\r\n:2\r\n", &strict),
+            Err(Error::of_unexpected(UNKNOWN, 8, b':'))
+        );
+    }
+
+    #[test]
+    fn value_parse_with_default_ignore_trailing_bytes() {
+        assert_eq!(
+            Value::parse_with("+OK\r\nEXTRA", &ParseOptions::default()),
+            Ok(Value::SimpleString("OK".into()))
+        );
+    }
+
+    #[test]
+    fn value_implement_clone() {
+        let value = Value::Array(vec![
+            Value::Nil,
+            Value::Array(vec![Value::Integer(1), Value::String("A".into())]),
+            Value::Error("Oh oh!".into()),
+        ]);
+        let clone = value.clone();
+
+        assert_eq!(clone, value);
+    }
+
+    #[test]
+    fn value_implement_from_str() {
+        assert_eq!(":10\r\n".parse(), Ok(Value::Integer(10)));
+        assert_eq!(
+            ":Yikes\r\n".parse::<Value>(),
+            Err(Error::of_type(INTEGER, 1))
+        );
+    }
+
+    #[test]
+    fn value_implement_from_i64() {
+        assert_eq!(Value::from(10), Value::Integer(10));
+        let value: Value = (-1).into();
+
+        assert_eq!(value, Value::Integer(-1));
+    }
+
+    #[test]
+    fn value_implement_from_string() {
+        assert_eq!(
+            Value::from(String::from("Hourly")),
+            Value::String("Hourly".into())
+        );
+    }
+
+    #[test]
+    fn value_implement_from_vec() {
+        assert_eq!(Value::from(vec![]), Value::Array(vec![]));
+        let value: Value = vec![10.into(), String::from("x").into()].into();
+
+        assert_eq!(
+            value,
+            Value::Array(vec![Value::Integer(10), Value::String("x".into())])
+        );
+    }
+
+    #[test]
+    fn value_try_into_i64() {
+        assert_eq!(i64::try_from(Value::Integer(5)), Ok(5));
+        assert_eq!(i64::try_from(Value::Nil), Err(Value::Nil));
+        assert_eq!(
+            i64::try_from(Value::String("5".into())),
+            Err(Value::String("5".into()))
+        );
+    }
+
+    #[test]
+    fn value_try_into_string() {
+        assert_eq!(
+            String::try_from(Value::String("Hourly".into())),
+            Ok("Hourly".into())
+        );
+        assert_eq!(
+            String::try_from(Value::Error("Oh oh!".into())),
+            Err(Value::Error("Oh oh!".into()))
+        );
+        assert_eq!(String::try_from(Value::Integer(1)), Err(Value::Integer(1)));
+    }
+
+    #[test]
+    fn value_try_into_vec() {
+        assert_eq!(
+            Vec::try_from(Value::Array(vec![Value::Nil])),
+            Ok(vec![Value::Nil])
+        );
+        assert_eq!(Vec::<Value>::try_from(Value::Nil), Err(Value::Nil));
+    }
+
+    #[test]
+    fn value_as_integer() {
+        assert_eq!(Value::Integer(447).as_integer(), Some(447));
+        assert_eq!(Value::String("447".into()).as_integer(), None);
+    }
+
+    #[test]
+    fn value_as_str() {
+        assert_eq!(Value::String("Hourly".into()).as_str(), Some("Hourly"));
+        assert_eq!(Value::Error("Oh oh!".into()).as_str(), None);
+        assert_eq!(Value::Nil.as_str(), None);
+    }
+
+    #[test]
+    fn value_as_array() {
+        let value = Value::Array(vec![Value::Nil, Value::Integer(1)]);
+
+        assert_eq!(value.as_array(), Some(&[Value::Nil, Value::Integer(1)][..]));
+        assert_eq!(Value::Integer(1).as_array(), None);
+    }
+
+    #[test]
+    fn value_is_nil() {
+        assert!(Value::Nil.is_nil());
+        assert!(!Value::Array(vec![]).is_nil());
+    }
+
+    #[test]
+    fn value_distinguish_simple_and_bulk_strings() {
+        let simple = Value::try_from("+OK\r\n").unwrap();
+        let bulk = Value::try_from("$2\r\nOK\r\n").unwrap();
+
+        assert_eq!(simple, Value::SimpleString("OK".into()));
+        assert_eq!(bulk, Value::String("OK".into()));
+        assert_ne!(simple, bulk);
+        assert_eq!(simple.to_resp_bytes(), b"+OK\r\n");
+        assert_eq!(bulk.to_resp_bytes(), b"$2\r\nOK\r\n");
+    }
+
+    #[test]
+    fn value_implement_display_as_resp() {
+        for source in [
+            "$-1\r\n",
+            ":-447\r\n",
+            "-Oh oh!\r\n",
+            "+Hourly\r\n",
+            "$26\r\nSi vis pacem,\r\npara bellum\r\n",
+            "*0\r\n",
+            "*2\r\n*3\r\n+A\r\n$1\r\nB\r\n$-1\r\n*1\r\n:3\r\n",
+        ] {
+            assert_eq!(Value::try_from(source).unwrap().to_string(), source);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn value_from_reader_consume_a_single_value() {
+        let mut reader = Cursor::new("*2\r\n$7\r\nOh\r\nOh!\r\n:1\r\n+EXTRA\r\n");
+
+        assert_eq!(
+            Value::from_reader(&mut reader).unwrap(),
+            Value::Array(vec![Value::String("Oh\r\nOh!".into()), Value::Integer(1)])
+        );
+        assert_eq!(reader.position(), 21);
+        assert_eq!(
+            Value::from_reader(&mut reader).unwrap(),
+            Value::SimpleString("EXTRA".into())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn value_from_reader_consume_resp3_values() {
+        let values = [
+            "%2\r\n+a\r\n:1\r\n+b\r\n*0\r\n",
+            "|1\r\n+ttl\r\n:60\r\n*1\r\n$-1\r\n",
+            "|0\r\n:1\r\n",
+            "*?\r\n:1\r\n*?\r\n.\r\n$1\r\n.\r\n.\r\n",
+            "$?\r\n;4\r\nOh\r\n\r\n;2\r\n!!\r\n;0\r\n",
+            "*2\r\n%0\r\n*-1\r\n",
+            "%1\r\n_\r\n*2\r\n:1\r\n_\r\n",
+            "$0\r\n\r\n",
+        ];
+        let source = values.concat();
+        let mut reader = Cursor::new(source.as_str());
+
+        for value in values {
+            let start = reader.position() as usize;
+
+            assert_eq!(
+                Value::from_reader(&mut reader).unwrap(),
+                Value::try_from(value).unwrap(),
+                "{value:?}"
+            );
+            assert_eq!(reader.position() as usize - start, value.len(), "{value:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn value_from_reader_report_errors() {
+        let incomplete = Value::from_reader(&mut Cursor::new("*2\r\n:1\r\n")).unwrap_err();
+        let malformed = Value::from_reader(&mut Cursor::new(":Yikes\r\n")).unwrap_err();
+
+        assert_eq!(incomplete.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(malformed.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            malformed.into_inner().unwrap().downcast::<Error>().unwrap(),
+            Box::new(Error::of_type(INTEGER, 1))
+        );
+
+        for (source, expected) in [
+            (
+                &b"+O\xffK\r\n"[..],
+                Error::of_unexpected(SIMPLE_STRING, 2, 0xff),
+            ),
+            (
+                b"*1\r\n$2\r\n\xff\xff\r\n",
+                Error::of_unexpected(BULK_STRING, 8, 0xff),
+            ),
+            (b"$2\r\nHello\r\n", Error::of_size(BULK_STRING, 6)),
+            (b"*1\r\n.\r\n", Error::of_unexpected(UNKNOWN, 4, b'.')),
+            (b"*x\r\n", Error::of_type(SIZE, 1)),
+            (b"%-2\r\n", Error::of_overflow(SIZE, 1)),
+            (b"$?\r\n:1\r\n", Error::of_unexpected(BULK_STRING, 4, b':')),
+            (b"+OK\n", Error::of_unexpected(SIMPLE_STRING, 3, b'\n')),
+        ] {
+            let error = Value::from_reader(&mut Cursor::new(source)).unwrap_err();
+
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{source:?}");
+            assert_eq!(
+                error.into_inner().unwrap().downcast::<Error>().unwrap(),
+                Box::new(expected),
+                "{source:?}"
+            );
+        }
+
+        let error = Value::from_reader(&mut Cursor::new("$5\r\nOops")).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn value_implement_try_from_incomplete_resp() {
+        assert_eq!(
+            "".try_into() as Result<Value, Error>,
+            Err(Error::of_incomplete(UNKNOWN, 0))
+        );
+        assert_eq!(
+            "+OK\r".try_into() as Result<Value, Error>,
+            Err(Error::of_incomplete(SIMPLE_STRING, 4))
+        );
+        assert_eq!(
+            "$4\r\nOo".try_into() as Result<Value, Error>,
+            Err(Error::of_incomplete(BULK_STRING, 6))
+        );
+        assert_eq!(
+            "$4\r\nOops\r".try_into() as Result<Value, Error>,
+            Err(Error::of_incomplete(BULK_STRING, 9))
+        );
+        assert_eq!(
+            "*2\r\n:1\r\n:2".try_into() as Result<Value, Error>,
+            Err(Error::of_incomplete(INTEGER, 10))
+        );
+        assert_eq!(
+            "+OK\nX".try_into() as Result<Value, Error>,
+            Err(Error::of_unexpected(SIMPLE_STRING, 3, b'\n'))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_integer_with_stray_line_feed_or_carriage_return() {
+        assert_eq!(
+            ":1\rX\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_type(INTEGER, 1))
+        );
+        assert_eq!(
+            ":1\n".try_into() as Result<Value, Error>,
+            Err(Error::of_type(INTEGER, 1))
+        );
+        assert_eq!(
+            ":1\r".try_into() as Result<Value, Error>,
+            Err(Error::of_incomplete(INTEGER, 3))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_bulk_string_with_multi_digit_size() {
+        let payload = "Lorem ipsum dolor sit amet. ".repeat(4);
+
+        assert_eq!(payload.len(), 112);
+        assert_eq!(
+            Value::try_from(format!("$112\r\n{payload}\r\n").as_str()),
+            Ok(Value::String(payload.clone()))
+        );
+        assert_eq!(
+            Value::try_from(format!("$100\r\n{}\r\n", &payload[..100]).as_str()),
+            Ok(Value::String(payload[..100].into()))
+        );
+        assert_eq!(
+            "$10\r\n0123456789\r\n".try_into(),
+            Ok(Value::String("0123456789".into()))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_bulk_string_with_leading_zeros_size() {
+        assert_eq!(
+            Value::parse_prefix("$04\r\nOops\r\n"),
+            Ok((Value::String("Oops".into()), 11))
+        );
+        assert_eq!(
+            Value::parse_prefix("$0007\r\nOh\r\nOh!\r\n"),
+            Ok((Value::String("Oh\r\nOh!".into()), 16))
+        );
+        assert_eq!(
+            "$04\r\nOopsy\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_size(BULK_STRING, 9))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_integer_with_overflow() {
+        assert_eq!(
+            ":99999999999999999999\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_overflow(INTEGER, 1))
+        );
+        assert_eq!(
+            ":-99999999999999999999\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_overflow(INTEGER, 1))
+        );
+        assert_eq!(
+            ":9223372036854775807\r\n".try_into(),
+            Ok(Value::Integer(i64::MAX))
+        );
+        assert_eq!(
+            "*1\r\n$99999999999999999999\r\n".try_into() as Result<Value, Error>,
+            Err(Error::of_overflow(SIZE, 5))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_empty_simple_string_and_error() {
+        assert_eq!("+\r\n".try_into(), Ok(Value::SimpleString("".into())));
+        assert_eq!("-\r\n".try_into(), Ok(Value::Error("".into())));
+        assert_eq!(
+            "*2\r\n+\r\n-\r\n".try_into(),
+            Ok(Value::Array(vec![
+                Value::SimpleString("".into()),
+                Value::Error("".into())
+            ]))
+        );
+        assert_eq!(Value::SimpleString("".into()).to_string(), "+\r\n");
+        assert_eq!(Value::Error("".into()).to_string(), "-\r\n");
+    }
+
+    #[test]
+    fn value_len() {
+        assert_eq!(Value::Array(vec![]).len(), Some(0));
+        assert_eq!(Value::Array(vec![Value::Nil]).len(), Some(1));
+        assert_eq!(
+            Value::Array(vec![Value::Nil, Value::Array(vec![Value::Nil])]).len(),
+            Some(2)
+        );
+        assert_eq!(Value::Map(vec![]).len(), Some(0));
+        assert_eq!(
+            Value::try_from("%2\r\n:1\r\n:2\r\n:3\r\n*0\r\n")
+                .unwrap()
+                .len(),
+            Some(2)
+        );
+        assert_eq!(Value::Nil.len(), None);
+        assert_eq!(Value::Integer(3).len(), None);
+        assert_eq!(Value::String("abc".into()).len(), None);
+    }
+
+    #[test]
+    fn value_is_empty() {
+        assert!(Value::Array(vec![]).is_empty());
+        assert!(!Value::Array(vec![Value::Nil]).is_empty());
+        assert!(Value::Map(vec![]).is_empty());
+        assert!(!Value::Map(vec![(Value::Nil, Value::Nil)]).is_empty());
+        assert!(!Value::Nil.is_empty());
+        assert!(!Value::String("".into()).is_empty());
+    }
+
+    #[test]
+    fn value_implement_into_iterator() {
+        let value = Value::Array(vec![
+            Value::Integer(1),
+            Value::Array(vec![Value::Integer(2), Value::Integer(3)]),
+        ]);
+        let mut values = vec![];
+
+        for value in value {
+            values.push(value);
+        }
+
+        assert_eq!(
+            values,
+            vec![
+                Value::Integer(1),
+                Value::Array(vec![Value::Integer(2), Value::Integer(3)])
+            ]
+        );
+        assert_eq!(Value::Integer(1).into_iter().next(), None);
+        assert_eq!(Value::Nil.into_iter().count(), 0);
+    }
+
+    #[test]
+    fn value_borrow_implement_into_iterator() {
+        let value = Value::Array(vec![
+            Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
+            Value::Array(vec![Value::Integer(3)]),
+        ]);
+        let mut sum = 0;
+
+        for array in &value {
+            for integer in array {
+                sum += integer.as_integer().unwrap();
+            }
+        }
+
+        assert_eq!(sum, 6);
+        assert_eq!((&Value::String("A".into())).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn value_command() {
+        assert_eq!(
+            Value::command(&["SET", "key", "val"]),
+            Value::Array(vec![
+                Value::String("SET".into()),
+                Value::String("key".into()),
+                Value::String("val".into()),
+            ])
+        );
+        assert_eq!(
+            Value::command(&["SET", "key", "val"]).to_resp_bytes(),
+            b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$3\r\nval\r\n"
+        );
+        assert_eq!(
+            Value::command(&["PING"]).to_string(),
+            "*1\r\n$4\r\nPING\r\n"
+        );
+    }
+
+    #[test]
+    fn value_parse_events() {
+        #[derive(Default)]
+        struct Events(Vec<std::string::String>);
+
+        impl Visitor<'_> for Events {
+            fn on_nil(&mut self) {
+                self.0.push("nil".into())
+            }
+
+            fn on_integer(&mut self, integer: i64) {
+                self.0.push(format!("integer {integer}"))
+            }
+
+            fn on_error(&mut self, message: &str) {
+                self.0.push(format!("error {message}"))
+            }
+
+            fn on_string(&mut self, string: &str) {
+                self.0.push(format!("string {string}"))
+            }
+
+            fn on_array_start(&mut self, len: usize) {
+                self.0.push(format!("array {len}"))
+            }
+
+            fn on_array_end(&mut self) {
+                self.0.push("end".into())
+            }
+        }
+
+        let mut events = Events::default();
+
+        assert_eq!(
+            Value::parse_events(
+                "*4\r\n$-1\r\n*0\r\n*2\r\n-Oh oh!\r\n+A\r\n$1\r\nB\r\n",
+                &mut events
+            ),
+            Ok(37)
+        );
+        assert_eq!(
+            events.0,
+            vec![
+                "array 4",
+                "nil",
+                "array 0",
+                "end",
+                "array 2",
+                "error Oh oh!",
+                "string A",
+                "end",
+                "string B",
+                "end"
+            ]
+        );
+    }
+
+    #[test]
+    fn value_parse_events_sum_integers() {
+        struct Sum(i64);
+
+        impl Visitor<'_> for Sum {
+            fn on_integer(&mut self, integer: i64) {
+                self.0 += integer;
+            }
+        }
+
+        let mut sum = Sum(0);
+
+        assert_eq!(
+            Value::parse_events("*3\r\n:1\r\n*2\r\n:2\r\n*1\r\n:3\r\n+4\r\n", &mut sum),
+            Ok(28)
+        );
+        assert_eq!(sum.0, 6);
+        assert_eq!(
+            Value::parse_events("*2\r\n:10\r\n", &mut sum),
+            Err(Error::of_incomplete(ARRAY, 9))
+        );
+        assert_eq!(sum.0, 16);
+    }
+
+    #[test]
+    fn value_walk() {
+        let value = Value::Array(vec![
+            Value::Integer(1),
+            Value::Array(vec![
+                Value::String("A".into()),
+                Value::Array(vec![Value::Integer(2)]),
+            ]),
+            Value::Integer(3),
+        ]);
+        let mut integers = vec![];
+        let mut count = 0;
+
+        value.walk(&mut |value| {
+            count += 1;
+            integers.extend(value.as_integer());
+        });
+
+        assert_eq!(integers, vec![1, 2, 3]);
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn value_depth() {
+        assert_eq!(Value::Nil.depth(), 1);
+        assert_eq!(Value::Integer(1).depth(), 1);
+        assert_eq!(Value::Array(vec![]).depth(), 1);
+        assert_eq!(Value::Array(vec![Value::Integer(1)]).depth(), 2);
+        assert_eq!(
+            Value::Array(vec![
+                Value::Integer(1),
+                Value::Array(vec![Value::Array(vec![Value::Nil]), Value::Nil]),
+                Value::Array(vec![]),
+            ])
+            .depth(),
+            4
+        );
+        assert_eq!(Value::try_from("*1\r\n*1\r\n:1\r\n").unwrap().depth(), 3);
+    }
+
+    #[test]
+    fn value_get() {
+        let value = Value::Array(vec![Value::Integer(1), Value::Nil]);
+
+        assert_eq!(value.get(0), Some(&Value::Integer(1)));
+        assert_eq!(value.get(1), Some(&Value::Nil));
+        assert_eq!(value.get(2), None);
+        assert_eq!(Value::Array(vec![]).get(0), None);
+        assert_eq!(Value::String("A".into()).get(0), None);
+    }
+
+    #[test]
+    fn value_try_path() {
+        let value = Value::Array(vec![
+            Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
+            Value::Array(vec![Value::Nil, Value::Array(vec![Value::Integer(3)])]),
+        ]);
+
+        assert_eq!(value.try_path(&[]), Some(&value));
+        assert_eq!(value.try_path(&[0, 1]), Some(&Value::Integer(2)));
+        assert_eq!(value.try_path(&[1, 1, 0]), Some(&Value::Integer(3)));
+        assert_eq!(value.try_path(&[1, 0]), Some(&Value::Nil));
+        assert_eq!(value.try_path(&[1, 0, 0]), None);
+        assert_eq!(value.try_path(&[0, 2]), None);
+        assert_eq!(value.try_path(&[2]), None);
+    }
+
+    #[test]
+    fn value_report_unexpected_byte() {
+        let error = Value::try_from("{\"a\":1}").unwrap_err();
+
+        assert_eq!(error, Error::of_unexpected(UNKNOWN, 0, b'{'));
+        assert_eq!(
+            error.to_string(),
+            "unexpected byte 0x7b in UNKNOWN at index 0"
+        );
+        assert_eq!(
+            Value::try_from("*2\r\n:1\r\n?\r\n"),
+            Err(Error::of_unexpected(UNKNOWN, 8, b'?'))
+        );
+        assert_eq!(
+            Value::try_from("-ERR\rBad\r\n"),
+            Err(Error::of_unexpected(ERROR, 4, b'\r'))
+        );
+    }
+
+    #[test]
+    fn value_compare_with_str_and_i64() {
+        assert_eq!(Value::String("OK".into()), "OK");
+        assert_eq!(Value::SimpleString("OK".into()), "OK");
+        assert!(Value::String("OK".into()) == *"OK");
+        assert_ne!(Value::String("OK".into()), "KO");
+        assert_ne!(Value::Error("OK".into()), "OK");
+        assert_ne!(Value::Integer(5), "5");
+        assert_ne!(Value::Nil, "");
+
+        assert_eq!(Value::Integer(-5), -5);
+        assert_ne!(Value::Integer(5), 6);
+        assert_ne!(Value::String("5".into()), 5);
+        assert_ne!(Value::Array(vec![Value::Integer(5)]), 5);
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_integer_with_sign_or_spaces() {
+        assert_eq!(Value::try_from(":+5\r\n"), Ok(Value::Integer(5)));
+        assert_eq!(Value::try_from(":-5\r\n"), Ok(Value::Integer(-5)));
+        assert_eq!(Value::try_from(": 5\r\n"), Err(Error::of_type(INTEGER, 1)));
+        assert_eq!(Value::try_from(":5 \r\n"), Err(Error::of_type(INTEGER, 1)));
+        assert_eq!(Value::try_from(":+ 5\r\n"), Err(Error::of_type(INTEGER, 1)));
+        assert_eq!(Value::try_from(":+-5\r\n"), Err(Error::of_type(INTEGER, 1)));
+        assert_eq!(Value::try_from(":-\r\n"), Err(Error::of_type(INTEGER, 1)));
+    }
+
+    #[test]
+    fn value_validate_as_try_from_does() {
+        for source in [
+            "$-1\r\n",
+            ":-447\r\n",
+            "-Oh oh!\r\n",
+            "+Hourly\r\n",
+            "$26\r\nSi vis pacem,\r\npara bellum\r\n",
+            "*3\r\n*0\r\n*1\r\n:1\r\n+A\r\n",
+            "",
+            ":0",
+            ":Yikes\r\n",
+            "$2\r\nHello\r\n",
+            "$3\r\nÂ\r\n",
+            "*2\r\n$-1\r\n",
+            "+Top\nBottom\r\n",
+            "{}",
+        ] {
+            assert_eq!(
+                Value::validate(source),
+                Value::try_from(source).map(|_| ()),
+                "{source:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn value_encoded_len_as_to_resp_bytes() {
+        for value in [
+            Value::Nil,
+            Value::Integer(0),
+            Value::Integer(9),
+            Value::Integer(10),
+            Value::Integer(-447),
+            Value::Integer(i64::MAX),
+            Value::Integer(i64::MIN),
+            Value::Error("".into()),
+            Value::Error("Oh oh!".into()),
+            Value::String("".into()),
+            Value::String("Si vis pacem,\r\npara bellum".into()),
+            Value::String("Â".repeat(10)),
+            Value::SimpleString("Hourly".into()),
+            Value::Array(vec![]),
+            Value::Array(vec![Value::Nil; 12]),
+            Value::Array(vec![
+                Value::Array(vec![Value::Integer(1), Value::String("A".into())]),
+                Value::Error("E".into()),
+            ]),
+            Value::Map(vec![]),
+            Value::Map(vec![
+                (
+                    Value::SimpleString("A".into()),
+                    Value::Array(vec![Value::Nil]),
+                ),
+                (
+                    Value::Integer(2),
+                    Value::Map(vec![(Value::Nil, Value::Nil)]),
+                ),
+            ]),
+        ] {
+            assert_eq!(
+                value.encoded_len(),
+                value.to_resp_bytes().len(),
+                "{value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_streamed_array() {
+        assert_eq!(
+            Value::parse_prefix("*?\r\n:1\r\n:2\r\n.\r\n"),
+            Ok((Value::Array(vec![Value::Integer(1), Value::Integer(2)]), 15))
+        );
+        assert_eq!(Value::try_from("*?\r\n.\r\n"), Ok(Value::Array(vec![])));
+        assert_eq!(
+            Value::try_from("*2\r\n*?\r\n*?\r\n.\r\n+A\r\n.\r\n:3\r\n"),
+            Ok(Value::Array(vec![
+                Value::Array(vec![Value::Array(vec![]), Value::SimpleString("A".into()),]),
+                Value::Integer(3),
+            ]))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_streamed_array_without_terminator() {
+        assert_eq!(
+            Value::try_from("*?\r\n:1\r\n"),
+            Err(Error::of_incomplete(ARRAY, 8))
+        );
+        assert_eq!(
+            Value::try_from("*?\r\n:1\r\n.\r"),
+            Err(Error::of_incomplete(ARRAY, 10))
+        );
+        assert_eq!(
+            Value::try_from("*?\r\n:1\r\n.!\r\n"),
+            Err(Error::of_unexpected(ARRAY, 9, b'!'))
+        );
+        assert_eq!(
+            Value::try_from(".\r\n"),
+            Err(Error::of_unexpected(UNKNOWN, 0, b'.'))
+        );
+        assert_eq!(
+            Value::try_from("*2\r\n:1\r\n.\r\n"),
+            Err(Error::of_unexpected(UNKNOWN, 8, b'.'))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_streamed_string() {
+        assert_eq!(
+            Value::parse_prefix("$?\r\n;4\r\nHell\r\n;1\r\no\r\n;0\r\n"),
+            Ok((Value::String("Hello".into()), 25))
+        );
+        assert_eq!(
+            Value::try_from("$?\r\n;0\r\n"),
+            Ok(Value::String("".into()))
+        );
+        assert_eq!(
+            Value::try_from("*2\r\n$?\r\n;4\r\nA\r\nB\r\n;0\r\n$?\r\n;0\r\n"),
+            Ok(Value::Array(vec![
+                Value::String("A\r\nB".into()),
+                Value::String("".into()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_malformed_streamed_string() {
+        assert_eq!(
+            Value::try_from("$?\r\n;4\r\nHell\r\n"),
+            Err(Error::of_incomplete(BULK_STRING, 14))
+        );
+        assert_eq!(
+            Value::try_from("$?\r\n;4\r\nHel"),
+            Err(Error::of_incomplete(BULK_STRING, 11))
+        );
+        assert_eq!(
+            Value::try_from("$?\r\n;4\r\nHello\r\n;0\r\n"),
+            Err(Error::of_size(BULK_STRING, 12))
+        );
+        assert_eq!(
+            Value::try_from("$?\r\n$4\r\nHell\r\n;0\r\n"),
+            Err(Error::of_unexpected(BULK_STRING, 4, b'$'))
+        );
+        assert_eq!(
+            Value::try_from(";4\r\nHell\r\n"),
+            Err(Error::of_unexpected(UNKNOWN, 0, b';'))
+        );
+    }
+
+    #[test]
+    fn value_type_name() {
+        let names = [
+            (Value::Nil, "nil"),
+            (Value::Integer(0), "integer"),
+            (Value::Error("".into()), "error"),
+            (Value::String("".into()), "string"),
+            (Value::SimpleString("".into()), "simple_string"),
+            (Value::Array(vec![]), "array"),
+            (Value::Map(vec![]), "map"),
+        ];
+
+        for (value, name) in names {
+            assert_eq!(value.type_name(), name);
+        }
+    }
+
+    #[test]
+    fn value_into_map() {
+        assert_eq!(Value::Array(vec![]).into_map(), Ok(vec![]));
+        assert_eq!(
+            Value::try_from("*4\r\n$1\r\nA\r\n$-1\r\n$1\r\nB\r\n*0\r\n")
+                .unwrap()
+                .into_map(),
+            Ok(vec![
+                (Value::String("A".into()), Value::Nil),
+                (Value::String("B".into()), Value::Array(vec![])),
+            ])
+        );
+
+        let odd = Value::Array(vec![
+            Value::Integer(1),
+            Value::Integer(2),
+            Value::Integer(3),
+        ]);
+
+        assert_eq!(odd.clone().into_map(), Err(odd));
+        assert_eq!(Value::Integer(1).into_map(), Err(Value::Integer(1)));
+    }
+
+    #[test]
+    fn value_default_to_nil() {
+        let mut value = Value::Integer(1);
+
+        assert_eq!(Value::default(), Value::Nil);
+        assert_eq!(core::mem::take(&mut value), Value::Integer(1));
+        assert_eq!(value, Value::Nil);
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_bytes() {
+        for source in [
+            "*2\r\n:1\r\n+Â\r\n",
+            "$3\r\nÂ\r\n",
+            ":X\r\n",
+            "*2\r\n:1\r\n",
+        ] {
+            assert_eq!(
+                Value::try_from(source.as_bytes()),
+                Value::try_from(source),
+                "{source:?}"
+            );
+            assert_eq!(
+                Value::try_from(source.to_string().into_bytes()),
+                Value::try_from(source),
+                "{source:?}"
+            );
+            assert_eq!(
+                Value::parse_owned(source.to_string()),
+                Value::try_from(source),
+                "{source:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_bytes_with_invalid_utf8() {
+        assert_eq!(
+            Value::try_from(&b"+OK\r\n\xff"[..]),
+            Ok(Value::SimpleString("OK".into()))
+        );
+        assert_eq!(
+            Value::try_from(&b"*2\r\n$2\r\n\xff\xff\r\n"[..]),
+            Err(Error::of_unexpected(BULK_STRING, 8, 0xff))
+        );
+        assert_eq!(
+            Value::try_from(&b"$2\r\n\xc3"[..]),
+            Err(Error::of_incomplete(BULK_STRING, 5))
+        );
+        assert_eq!(
+            Value::try_from(&b":\xff\r\n"[..]),
+            Err(Error::of_unexpected(INTEGER, 1, 0xff))
+        );
+    }
+
+    #[test]
+    fn value_error_code() {
+        let code = |message: &str| Value::Error(message.into()).error_code().map(String::from);
+
+        assert_eq!(code("ERR unknown command 'FOO'"), Some("ERR".into()));
+        assert_eq!(
+            code("NOAUTH Authentication required."),
+            Some("NOAUTH".into())
+        );
+        assert_eq!(code("LOADING"), Some("LOADING".into()));
+        assert_eq!(code("Oh oh!"), None);
+        assert_eq!(code("ERR: oops"), None);
+        assert_eq!(code(" ERR oops"), None);
+        assert_eq!(code(""), None);
+        assert_eq!(Value::SimpleString("ERR oops".into()).error_code(), None);
+    }
+
+    #[test]
+    fn value_as_error() {
+        assert_eq!(Value::Error("Oh oh!".into()).as_error(), Some("Oh oh!"));
+        assert_eq!(Value::Error("".into()).as_error(), Some(""));
+        assert_eq!(Value::SimpleString("Oh oh!".into()).as_error(), None);
+        assert_eq!(Value::String("Oh oh!".into()).as_error(), None);
+        assert_eq!(Value::Nil.as_error(), None);
+    }
+
+    #[test]
+    fn value_parse_with_bare_lf() {
+        let lenient = ParseOptions {
+            bare_lf: true,
+            ..ParseOptions::default()
+        };
+
+        assert_eq!(
+            Value::parse_with(":5\n", &ParseOptions::default()),
+            Err(Error::of_type(INTEGER, 1))
+        );
+        assert_eq!(
+            Value::parse_with("+OK\n", &ParseOptions::default()),
+            Err(Error::of_unexpected(SIMPLE_STRING, 3, b'\n'))
+        );
+        assert_eq!(Value::parse_with(":5\n", &lenient), Ok(Value::Integer(5)));
+        assert_eq!(
+            Value::parse_with("*3\r\n+OK\n-ERR\n$2\nHi\r\n", &lenient),
+            Ok(Value::Array(vec![
+                Value::SimpleString("OK".into()),
+                Value::Error("ERR".into()),
+                Value::String("Hi".into()),
+            ]))
+        );
+        assert_eq!(
+            Value::parse_with("$2\nHi\n", &lenient),
+            Err(Error::of_size(BULK_STRING, 5))
+        );
+        assert_eq!(
+            Value::parse_with("+O\rK\n", &lenient),
+            Err(Error::of_unexpected(SIMPLE_STRING, 2, b'\r'))
+        );
+        assert_eq!(
+            Value::parse_with("*?\n:1\n*?\r\n.\n.\n", &lenient),
+            Ok(Value::Array(vec![Value::Integer(1), Value::Array(vec![])]))
+        );
+        assert_eq!(
+            Value::parse_with("$?\n;2\nHi\r\n;1\n!\r\n;0\n", &lenient),
+            Ok(Value::String("Hi!".into()))
+        );
+        assert_eq!(
+            Value::parse_with("*?\n:1\r\n.\r\n", &ParseOptions::default()),
+            Err(Error::of_type(SIZE, 1))
+        );
+        assert_eq!(
+            Value::parse_with("$?\n;0\r\n", &ParseOptions::default()),
+            Err(Error::of_type(SIZE, 1))
+        );
+    }
+
+    #[test]
+    fn value_parse_inline() {
+        assert_eq!(
+            Value::parse_inline("PING\r\n"),
+            Ok(Value::Array(vec![Value::String("PING".into())]))
+        );
+        assert_eq!(
+            Value::parse_inline("\tSET key\tval \r\n:1\r\n"),
+            Ok(Value::Array(vec![
+                Value::String("SET".into()),
+                Value::String("key".into()),
+                Value::String("val".into()),
+            ]))
+        );
+        assert_eq!(Value::parse_inline("\r\n"), Ok(Value::Array(vec![])));
+        assert_eq!(
+            Value::parse_inline("PING"),
+            Err(Error::of_incomplete(UNKNOWN, 4))
+        );
+        assert_eq!(
+            Value::parse_inline("PING\n"),
+            Err(Error::of_unexpected(UNKNOWN, 4, b'\n'))
+        );
+        assert_eq!(
+            Value::parse_inline("PI\rNG\r\n"),
+            Err(Error::of_unexpected(UNKNOWN, 2, b'\r'))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_map() {
+        let source = "%2\r\n+first\r\n:1\r\n$6\r\nsecond\r\n*1\r\n%0\r\n";
+        let value = Value::try_from(source).unwrap();
+
+        assert_eq!(
+            value,
+            Value::Map(vec![
+                (Value::SimpleString("first".into()), Value::Integer(1)),
+                (
+                    Value::String("second".into()),
+                    Value::Array(vec![Value::Map(vec![])])
+                ),
+            ])
+        );
+        assert_eq!(value.to_string(), source);
+        assert_eq!(value.depth(), 3);
+        assert_eq!(
+            Value::try_from("*2\r\n%1\r\n:1\r\n:2\r\n:3\r\n"),
+            Ok(Value::Array(vec![
+                Value::Map(vec![(Value::Integer(1), Value::Integer(2))]),
+                Value::Integer(3),
+            ]))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_incomplete_map() {
+        assert_eq!(
+            Value::try_from("%2\r\n+first\r\n:1\r\n+second\r\n"),
+            Err(Error::of_incomplete(MAP, 25))
+        );
+        assert_eq!(
+            Value::try_from("*2\r\n%1\r\n:1\r\n:2\r\n"),
+            Err(Error::of_incomplete(ARRAY, 16))
+        );
+        assert_eq!(Value::try_from("%X\r\n"), Err(Error::of_type(SIZE, 1)));
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_negative_map_size() {
+        for (source, error) in [
+            ("%-1\r\n", Error::of_overflow(SIZE, 1)),
+            ("%-2\r\n", Error::of_overflow(SIZE, 1)),
+            ("%-5\r\n:1\r\n:2\r\n", Error::of_overflow(SIZE, 1)),
+            ("%-9223372036854775808\r\n", Error::of_overflow(SIZE, 1)),
+            ("*1\r\n%-1\r\n", Error::of_overflow(SIZE, 5)),
+        ] {
+            assert_eq!(Value::try_from(source), Err(error.clone()), "{source:?}");
+            assert_eq!(Value::frame_len(source), Err(error), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn value_iter_map() {
+        let value = Value::Map(vec![
+            (Value::SimpleString("a".into()), Value::Integer(1)),
+            (Value::SimpleString("b".into()), Value::Nil),
+        ]);
+
+        assert_eq!(
+            value.iter_map().unwrap().collect::<Vec<_>>(),
+            vec![
+                (&Value::SimpleString("a".into()), &Value::Integer(1)),
+                (&Value::SimpleString("b".into()), &Value::Nil),
+            ]
+        );
+        assert!(Value::Array(vec![Value::Integer(1), Value::Nil])
+            .iter_map()
+            .is_none());
+        assert_eq!(
+            value.clone().into_map(),
+            Ok(vec![
+                (Value::SimpleString("a".into()), Value::Integer(1)),
+                (Value::SimpleString("b".into()), Value::Nil),
+            ])
+        );
+    }
+
+    #[test]
+    fn value_flatten_a_single_level() {
+        let value = Value::Array(vec![
+            Value::Integer(1),
+            Value::Array(vec![]),
+            Value::Array(vec![
+                Value::String("A".into()),
+                Value::Array(vec![Value::Nil]),
+            ]),
+            Value::Map(vec![(Value::Nil, Value::Nil)]),
+            Value::Array(vec![Value::Integer(2)]),
+        ]);
+
+        assert_eq!(
+            value.flatten(),
+            Value::Array(vec![
+                Value::Integer(1),
+                Value::String("A".into()),
+                Value::Array(vec![Value::Nil]),
+                Value::Map(vec![(Value::Nil, Value::Nil)]),
+                Value::Integer(2),
+            ])
+        );
+        assert_eq!(Value::Array(vec![]).flatten(), Value::Array(vec![]));
+        assert_eq!(Value::Integer(1).flatten(), Value::Integer(1));
+    }
+
+    #[test]
+    fn value_collect_and_extend_arrays() {
+        let value: Value = vec![Value::Nil, Value::Integer(1)].into_iter().collect();
+
+        assert_eq!(value, Value::Array(vec![Value::Nil, Value::Integer(1)]));
+        assert_eq!(core::iter::empty().collect::<Value>(), Value::Array(vec![]));
+
+        let mut value = Value::Array(vec![]);
+
+        value.extend(vec![Value::Integer(1)]);
+        value.extend(value.clone());
+        assert_eq!(
+            value,
+            Value::Array(vec![Value::Integer(1), Value::Integer(1)])
+        );
+
+        let mut value = Value::SimpleString("A".into());
+
+        value.extend([Value::Integer(1)]);
+        assert_eq!(
+            value,
+            Value::Array(vec![Value::SimpleString("A".into()), Value::Integer(1)])
+        );
+
+        let mut value = Value::default();
+
+        value.extend([Value::Integer(1)]);
+        assert_eq!(value, Value::Array(vec![Value::Integer(1)]));
+
+        let mut value = Value::Nil;
+
+        value.extend([]);
+        assert_eq!(value, Value::Array(vec![]));
+    }
+
+    #[test]
+    fn value_parse_all() {
+        assert_eq!(
+            Value::parse_all("*1\r\n:1\r\n-Oh oh!\r\n$2\r\nHi\r\n"),
+            Ok((
+                vec![
+                    Value::Array(vec![Value::Integer(1)]),
+                    Value::Error("Oh oh!".into()),
+                    Value::String("Hi".into()),
+                ],
+                25
+            ))
+        );
+        assert_eq!(Value::parse_all(""), Ok((vec![], 0)));
+    }
+
+    #[test]
+    fn value_parse_all_stop_before_partial_value() {
+        assert_eq!(
+            Value::parse_all(":1\r\n*2\r\n:2\r\n"),
+            Ok((vec![Value::Integer(1)], 4))
+        );
+        assert_eq!(
+            Value::parse_all(":1\r\n:2\r\n:X\r\n"),
+            Err(Error::of_type(INTEGER, 9))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_array_underflow_index() {
+        for (source, node) in [
+            ("*1\r\n", ARRAY),
+            ("*2\r\n$-1\r\n", ARRAY),
+            ("*3\r\n:1\r\n+A\r\n", ARRAY),
+            ("*2\r\n*1\r\n:1\r\n", ARRAY),
+            ("*1\r\n*2\r\n:1\r\n", ARRAY),
+            ("*1\r\n*1\r\n*1\r\n", ARRAY),
+            ("*?\r\n*1\r\n:1\r\n", ARRAY),
+            ("*1\r\n%1\r\n:1\r\n", MAP),
+        ] {
+            assert_eq!(
+                Value::try_from(source),
+                Err(Error::of_incomplete(node, source.len())),
+                "{source:?}"
+            );
+        }
+
+        // An incomplete entry reports its own node instead.
+        assert_eq!(
+            Value::try_from("*2\r\n:1\r\n:2"),
+            Err(Error::of_incomplete(INTEGER, 10))
+        );
+    }
+
+    #[test]
+    fn value_count_nodes() {
+        assert_eq!(Value::Nil.count_nodes(), 1);
+        assert_eq!(Value::Array(vec![]).count_nodes(), 1);
+        assert_eq!(
+            Value::Array(vec![
+                Value::Integer(1),
+                Value::Array(vec![Value::Nil, Value::Array(vec![])]),
+                Value::Map(vec![(
+                    Value::SimpleString("A".into()),
+                    Value::Array(vec![Value::Error("E".into())]),
+                )]),
+            ])
+            .count_nodes(),
+            9
+        );
+    }
+
+    #[test]
+    fn value_hash_in_set() {
+        let mut set = std::collections::HashSet::new();
+
+        assert!(set.insert(Value::String("A".into())));
+        assert!(set.insert(Value::SimpleString("A".into())));
+        assert!(set.insert(Value::Array(vec![Value::Nil, Value::Integer(1)])));
+        assert!(!set.insert(Value::String("A".into())));
+        assert!(!set.insert(Value::try_from("*2\r\n$-1\r\n:1\r\n").unwrap()));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&Value::SimpleString("A".into())));
+    }
+
+    #[test]
+    fn value_map_strings() {
+        let value = Value::Array(vec![
+            Value::String("a".into()),
+            Value::Array(vec![Value::SimpleString("b".into()), Value::Integer(1)]),
+            Value::Error("ERR c".into()),
+            Value::Map(vec![(Value::String("d".into()), Value::String("e".into()))]),
+            Value::Nil,
+        ]);
+        let mut calls = 0;
+
+        assert_eq!(
+            value.map_strings(&mut |string| {
+                calls += 1;
+                string.to_uppercase()
+            }),
+            Value::Array(vec![
+                Value::String("A".into()),
+                Value::Array(vec![Value::SimpleString("B".into()), Value::Integer(1)]),
+                Value::Error("ERR c".into()),
+                Value::Map(vec![(Value::String("D".into()), Value::String("E".into()))]),
+                Value::Nil,
+            ])
+        );
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn value_pretty() {
+        let value = Value::Array(vec![
+            Value::Nil,
+            Value::Array(vec![Value::Error("Oh oh!".into()), Value::Array(vec![])]),
+            Value::Map(vec![(Value::SimpleString("a".into()), Value::Integer(1))]),
+        ]);
+
+        assert_eq!(
+            value.pretty(),
+            [
+                "Array(3)",
+                "  Nil",
+                "  Array(2)",
+                "    Error(\"Oh oh!\")",
+                "    Array(0)",
+                "  Map(1)",
+                "    SimpleString(\"a\")",
+                "    Integer(1)",
+            ]
+            .join("\n")
+        );
+        assert_eq!(Value::Integer(-1).pretty(), "Integer(-1)");
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_simple_string_with_stray_line_ending() {
+        for (source, index, byte) in [
+            ("+ab\r\r\n", 3, b'\r'),
+            ("+ab\n\r\n", 3, b'\n'),
+            ("+a\rb\r\r\n", 2, b'\r'),
+            ("+\r\r\n", 1, b'\r'),
+            ("+\n\r\n", 1, b'\n'),
+        ] {
+            assert_eq!(
+                Value::try_from(source),
+                Err(Error::of_unexpected(SIMPLE_STRING, index, byte)),
+                "{source:?}"
+            );
+        }
+
+        assert_eq!(
+            Value::try_from("-ab\r\r\n"),
+            Err(Error::of_unexpected(ERROR, 3, b'\r'))
+        );
+        assert_eq!(
+            Value::parse_prefix("+ab\r\n\r\n"),
+            Ok((Value::SimpleString("ab".into()), 5))
+        );
+    }
+
+    #[test]
+    fn value_into_string() {
+        assert_eq!(
+            Value::String("Oh\r\nOh!".into()).into_string(),
+            Ok("Oh\r\nOh!".into())
+        );
+        assert_eq!(
+            Value::SimpleString("Hourly".into()).into_string(),
+            Ok("Hourly".into())
+        );
+        assert_eq!(Value::Integer(447).into_string(), Err(Value::Integer(447)));
+        assert_eq!(
+            Value::Error("Oh oh!".into()).into_string(),
+            Err(Value::Error("Oh oh!".into()))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_null_array() {
+        assert_eq!(Value::parse_prefix("*-1\r\n"), Ok((Value::Nil, 5)));
+        assert_eq!(
+            Value::try_from("*2\r\n*-1\r\n:1\r\n"),
+            Ok(Value::Array(vec![Value::Nil, Value::Integer(1)]))
+        );
+        assert_eq!(
+            Value::try_from("%1\r\n+a\r\n*-1\r\n"),
+            Ok(Value::Map(vec![(
+                Value::SimpleString("a".into()),
+                Value::Nil
+            )]))
+        );
+        assert_eq!(
+            Value::try_from("*?\r\n*-1\r\n.\r\n"),
+            Ok(Value::Array(vec![Value::Nil]))
+        );
+    }
+
+    #[test]
+    fn value_parse_with_max_array_len() {
+        let limited = ParseOptions {
+            max_array_len: Some(2),
+            ..ParseOptions::default()
+        };
+
+        assert_eq!(
+            Value::parse_with("*2\r\n:1\r\n:2\r\n", &limited),
+            Ok(Value::Array(vec![Value::Integer(1), Value::Integer(2)]))
+        );
+        assert_eq!(
+            Value::parse_with("*2000000000\r\n", &limited),
+            Err(Error::of_overflow(ARRAY, 1))
+        );
+        assert_eq!(
+            Value::parse_with("*1\r\n*3\r\n:1\r\n:2\r\n:3\r\n", &limited),
+            Err(Error::of_overflow(ARRAY, 5))
+        );
+        assert_eq!(
+            Value::parse_with("%3\r\n", &limited),
+            Err(Error::of_overflow(MAP, 1))
+        );
+        assert_eq!(
+            Value::parse_with("*2000000000\r\n", &ParseOptions::default()),
+            Err(Error::of_incomplete(ARRAY, 13))
+        );
+    }
+
+    #[test]
+    fn value_parse_with_max_depth() {
+        let limited = ParseOptions {
+            max_depth: Some(1),
+            ..ParseOptions::default()
+        };
+
+        assert_eq!(
+            Value::parse_with("*2\r\n:1\r\n%0\r\n", &ParseOptions::default()),
+            Ok(Value::Array(vec![Value::Integer(1), Value::Map(vec![])]))
+        );
+        for (source, error) in [
+            ("*2\r\n:1\r\n%0\r\n", Error::of_overflow(MAP, 8)),
+            ("*1\r\n*0\r\n", Error::of_overflow(ARRAY, 4)),
+            ("%1\r\n+a\r\n*?\r\n.\r\n", Error::of_overflow(ARRAY, 8)),
+            (
+                "*1\r\n|1\r\n+a\r\n:1\r\n:2\r\n",
+                Error::of_overflow(ATTRIBUTE, 4),
+            ),
+        ] {
+            assert_eq!(
+                Value::parse_with(source, &limited),
+                Err(error),
+                "{source:?}"
+            );
+        }
+        assert_eq!(
+            Value::parse_with("*1\r\n*1\r\n:1\r\n", &limited),
+            Err(Error::of_overflow(ARRAY, 4))
+        );
+        assert_eq!(
+            Value::parse_with("*1\r\n:1\r\n", &limited),
+            Ok(Value::Array(vec![Value::Integer(1)]))
+        );
+    }
+
+    #[test]
+    fn value_parse_deeply_nested_arrays() {
+        let max = ParseOptions::DEFAULT_MAX_DEPTH;
+        let source = format!("{}:1\r\n", "*1\r\n".repeat(max));
+        let value = Value::try_from(source.as_str()).unwrap();
+
+        // Built values are handled recursively, which the default depth
+        // limit keeps safe on a test thread stack.
+        assert_eq!(value.depth(), max + 1);
+        assert_eq!(value.clone(), value);
+        assert_eq!(value.to_string(), source);
+        assert!(format!("{value:?}").ends_with(&"])".repeat(max)));
+        drop(value);
+
+        let deeper = format!("{}:1\r\n", "*1\r\n".repeat(max + 1));
+
+        assert_eq!(
+            Value::try_from(deeper.as_str()),
+            Err(Error::of_overflow(ARRAY, 4 * max))
+        );
+
+        let deepest = "*1\r\n".repeat(1_000_000);
+
+        assert_eq!(
+            Value::try_from(deepest.as_str()),
+            Err(Error::of_overflow(ARRAY, 4 * max))
+        );
+        assert_eq!(
+            Value::validate(&deepest),
+            Err(Error::of_overflow(ARRAY, 4 * max))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn value_from_reader_deeply_nested_arrays() {
+        let max = ParseOptions::DEFAULT_MAX_DEPTH;
+        let error = Value::from_reader(&mut Cursor::new("*1\r\n".repeat(1_000_000))).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.into_inner().unwrap().downcast::<Error>().unwrap(),
+            Box::new(Error::of_overflow(ARRAY, 4 * max))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_malformed_nil() {
+        for (source, error) in [
+            ("$-1", Error::of_incomplete(NIL, 3)),
+            ("$-1\r", Error::of_incomplete(NIL, 4)),
+            ("$-1\rX", Error::of_unexpected(NIL, 4, b'X')),
+            ("$-1X\r\n", Error::of_unexpected(NIL, 3, b'X')),
+            ("$-1\n", Error::of_unexpected(NIL, 3, b'\n')),
+            ("*-1", Error::of_incomplete(NIL, 3)),
+            ("*-1 \r\n", Error::of_unexpected(NIL, 3, b' ')),
+            (
+                "*2\r\n:1\r\n$-1\r:2\r\n",
+                Error::of_unexpected(NIL, 12, b':'),
+            ),
+        ] {
+            assert_eq!(Value::try_from(source), Err(error), "{source:?}");
+        }
+
+        let lenient = ParseOptions {
+            bare_lf: true,
+            ..ParseOptions::default()
+        };
+
+        assert_eq!(Value::parse_with("*-1\n", &lenient), Ok(Value::Nil));
+        assert_eq!(Value::parse_with("$-1\n", &lenient), Ok(Value::Nil));
+    }
+
+    #[test]
+    fn value_implement_ord() {
+        let mut values = vec![
+            Value::Map(vec![]),
+            Value::Array(vec![Value::Integer(1), Value::Nil]),
+            Value::Array(vec![Value::Integer(1)]),
+            Value::SimpleString("OK".into()),
+            Value::String("b".into()),
+            Value::String("a".into()),
+            Value::Error("ERR".into()),
+            Value::Integer(7),
+            Value::Integer(-7),
+            Value::Nil,
+        ];
+
+        values.sort();
+
+        assert_eq!(
+            values,
+            vec![
+                Value::Nil,
+                Value::Integer(-7),
+                Value::Integer(7),
+                Value::Error("ERR".into()),
+                Value::String("a".into()),
+                Value::String("b".into()),
+                Value::SimpleString("OK".into()),
+                Value::Array(vec![Value::Integer(1)]),
+                Value::Array(vec![Value::Integer(1), Value::Nil]),
+                Value::Map(vec![]),
+            ]
+        );
+        assert!(Value::String("z".into()) < Value::SimpleString("a".into()));
+    }
+
+    #[test]
+    fn value_simple_string() {
+        assert_eq!(
+            Value::simple_string("Nina Simone"),
+            Ok(Value::SimpleString("Nina Simone".into()))
+        );
+        assert_eq!(Value::simple_string(""), Ok(Value::SimpleString("".into())));
+        assert_eq!(
+            Value::simple_string("Si vis pacem,\npara bellum"),
+            Err(Error::of_unexpected(SIMPLE_STRING, 13, b'\n'))
+        );
+        assert_eq!(
+            Value::simple_string("OK\r"),
+            Err(Error::of_unexpected(SIMPLE_STRING, 2, b'\r'))
+        );
     }
 
     #[test]
-    fn value_implement_try_from_resp_simple_string_with_line_feed_or_carriage_return_in_value() {
+    fn value_parse_advance() {
+        let mut input = "*1\r\n:1\r\n$2\r\nOK\r\n$5\r\nHel";
+
         assert_eq!(
-            "+Top\nBottom\r\n".try_into() as Result<Value, Error>,
-            Err(Error::of_unexpected(SIMPLE_STRING, 4))
+            Value::parse_advance(&mut input),
+            Ok(Value::Array(vec![Value::Integer(1)]))
         );
+        assert_eq!(input, "$2\r\nOK\r\n$5\r\nHel");
         assert_eq!(
-            "+Top\rBottom\r\n".try_into() as Result<Value, Error>,
-            Err(Error::of_unexpected(SIMPLE_STRING, 4))
+            Value::parse_advance(&mut input),
+            Ok(Value::String("OK".into()))
+        );
+        assert_eq!(input, "$5\r\nHel");
+        assert_eq!(
+            Value::parse_advance(&mut input),
+            Err(Error::of_incomplete(BULK_STRING, 7))
         );
+        assert_eq!(input, "$5\r\nHel");
     }
 
     #[test]
-    fn value_implement_try_from_resp_with_invalid_size_type() {
+    fn value_reencode() {
+        for source in [
+            "$-1\r\n",
+            ":-447\r\n",
+            "-ERR oops\r\n",
+            "$0\r\n\r\n",
+            "$26\r\nSi vis pacem,\r\npara bellum\r\n",
+            "+Hourly\r\n",
+            "*0\r\n",
+            "*2\r\n*1\r\n:1\r\n+OK\r\n",
+            "%1\r\n+key\r\n:1\r\n",
+        ] {
+            assert_eq!(Value::reencode(source).as_deref(), Ok(source));
+        }
+
+        for (source, canonical) in [
+            ("*-1\r\n", "$-1\r\n"),
+            (":+5\r\n", ":5\r\n"),
+            (":-0\r\n", ":0\r\n"),
+            ("$?\r\n;2\r\nOK\r\n;0\r\n", "$2\r\nOK\r\n"),
+            ("*?\r\n:1\r\n.\r\n", "*1\r\n:1\r\n"),
+            ("+OK\r\nEXTRA", "+OK\r\n"),
+        ] {
+            assert_eq!(Value::reencode(source).as_deref(), Ok(canonical));
+        }
+
         assert_eq!(
-            "*!\r\n$-1\r\n".try_into() as Result<Value, Error>,
-            Err(Error::of_type(SIZE, 1))
+            Value::reencode("*2\r\n:1\r\n"),
+            Err(Error::of_incomplete(ARRAY, 8))
+        );
+    }
+
+    #[test]
+    fn value_as_bool() {
+        assert_eq!(Value::Integer(0).as_bool(), Some(false));
+        assert_eq!(Value::Integer(1).as_bool(), Some(true));
+        assert_eq!(Value::Integer(2).as_bool(), None);
+        assert_eq!(Value::Integer(-1).as_bool(), None);
+        assert_eq!(Value::Nil.as_bool(), None);
+        assert_eq!(Value::String("1".into()).as_bool(), None);
+        assert_eq!(Value::Array(vec![Value::Integer(1)]).as_bool(), None);
+    }
+
+    #[test]
+    fn value_parse_attributed() {
+        let source = "|1\r\n+key-popularity\r\n%1\r\n$1\r\na\r\n:19\r\n*2\r\n:2039\r\n:9543\r\n";
+        let array = Value::Array(vec![Value::Integer(2039), Value::Integer(9543)]);
+
+        assert_eq!(Value::try_from(source), Ok(array.clone()));
+        assert_eq!(
+            Value::parse_attributed(source),
+            Ok((
+                array,
+                Some(vec![(
+                    Value::SimpleString("key-popularity".into()),
+                    Value::Map(vec![(Value::String("a".into()), Value::Integer(19))])
+                )])
+            ))
         );
+        assert_eq!(Value::parse_prefix(source).map(|(_, size)| size), Ok(55));
         assert_eq!(
-            "$!\r\n$-1\r\n".try_into() as Result<Value, Error>,
-            Err(Error::of_type(SIZE, 1))
+            Value::parse_attributed(":1\r\n"),
+            Ok((Value::Integer(1), None))
+        );
+        assert_eq!(
+            Value::parse_attributed("|0\r\n:1\r\n"),
+            Ok((Value::Integer(1), Some(vec![])))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_nested_attribute() {
+        assert_eq!(
+            Value::parse_attributed("*2\r\n|1\r\n+a\r\n:1\r\n:2\r\n%1\r\n:3\r\n|0\r\n:4\r\n"),
+            Ok((
+                Value::Array(vec![
+                    Value::Integer(2),
+                    Value::Map(vec![(Value::Integer(3), Value::Integer(4))])
+                ]),
+                None
+            ))
+        );
+        assert_eq!(
+            Value::try_from("|1\r\n+a\r\n"),
+            Err(Error::of_incomplete(ATTRIBUTE, 8))
+        );
+        assert_eq!(
+            Value::try_from("|1\r\n+a\r\n:1\r\n"),
+            Err(Error::of_incomplete(UNKNOWN, 12))
+        );
+        assert_eq!(
+            Value::try_from("*1\r\n|1\r\n+a\r\n:1\r\n"),
+            Err(Error::of_incomplete(ARRAY, 16))
+        );
+        assert_eq!(Value::try_from("|x\r\n"), Err(Error::of_type(SIZE, 1)));
+        assert_eq!(
+            Value::try_from("*?\r\n|1\r\n+a\r\n+b\r\n.\r\n"),
+            Err(Error::of_unexpected(ATTRIBUTE, 16, b'.'))
+        );
+        assert_eq!(
+            Value::try_from("*?\r\n:1\r\n|0\r\n.\r\n"),
+            Err(Error::of_unexpected(ATTRIBUTE, 12, b'.'))
+        );
+        assert_eq!(
+            Value::try_from("*?\r\n|1\r\n+a\r\n+b\r\n:1\r\n.\r\n"),
+            Ok(Value::Array(vec![Value::Integer(1)]))
+        );
+        assert_eq!(
+            Value::try_from("|-1\r\n+OK\r\n"),
+            Err(Error::of_overflow(SIZE, 1))
+        );
+        assert_eq!(
+            Value::try_from("*1\r\n|-2\r\n+a\r\n:1\r\n+OK\r\n"),
+            Err(Error::of_overflow(SIZE, 5))
+        );
+        assert_eq!(
+            Value::frame_len("|-9223372036854775808\r\n"),
+            Err(Error::of_overflow(SIZE, 1))
+        );
+    }
+
+    #[test]
+    fn value_try_from_exact() {
+        assert_eq!(
+            Value::try_from_exact("*2\r\n:1\r\n$2\r\nOK\r\n"),
+            Ok(Value::Array(vec![
+                Value::Integer(1),
+                Value::String("OK".into())
+            ]))
+        );
+        assert_eq!(
+            Value::try_from_exact("$2\r\nOK\r\n\r\n"),
+            Err(Error::of_unexpected(UNKNOWN, 8, b'\r'))
+        );
+        assert_eq!(
+            Value::try_from_exact("*2\r\n:1\r\n"),
+            Err(Error::of_incomplete(ARRAY, 8))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_bulk_string_out_of_range_size() {
+        assert_eq!(
+            Value::try_from("$10\r\nabc\r\n"),
+            Err(Error::of_incomplete(BULK_STRING, 10))
+        );
+        assert_eq!(
+            Value::try_from("$9223372036854775807\r\nabc\r\n"),
+            Err(Error::of_incomplete(BULK_STRING, 27))
+        );
+        assert_eq!(
+            Value::try_from("$18446744073709551615\r\nabc\r\n"),
+            Err(Error::of_overflow(SIZE, 1))
+        );
+        assert_eq!(
+            Value::try_from("$-2\r\nab\r\n"),
+            Err(Error::of_overflow(SIZE, 1))
+        );
+        assert_eq!(
+            Value::try_from("$-9223372036854775808\r\n"),
+            Err(Error::of_overflow(SIZE, 1))
+        );
+        assert_eq!(
+            Value::try_from("*1\r\n$?\r\n;-3\r\nabc\r\n"),
+            Err(Error::of_overflow(SIZE, 9))
+        );
+        assert_eq!(Value::try_from("*-2\r\n"), Err(Error::of_overflow(SIZE, 1)));
+        assert_eq!(
+            Value::try_from("*-9223372036854775808\r\n"),
+            Err(Error::of_overflow(SIZE, 1))
+        );
+        assert_eq!(
+            Value::try_from("*1\r\n*-2\r\n"),
+            Err(Error::of_overflow(SIZE, 5))
+        );
+        assert_eq!(
+            Value::frame_len("*-2\r\n"),
+            Err(Error::of_overflow(SIZE, 1))
+        );
+        assert_eq!(
+            Value::parse_all("*-2\r\n"),
+            Err(Error::of_overflow(SIZE, 1))
+        );
+    }
+
+    #[test]
+    fn value_eq_unordered() {
+        let a = Value::String("a".into());
+        let b = Value::SimpleString("b".into());
+        let nested = Value::Array(vec![Value::Integer(1), Value::Integer(2)]);
+        let value = Value::Array(vec![a.clone(), b.clone(), nested.clone(), a.clone()]);
+
+        assert!(value.eq_unordered(&value));
+        assert!(value.eq_unordered(&Value::Array(vec![
+            nested.clone(),
+            a.clone(),
+            a.clone(),
+            b.clone()
+        ])));
+        assert!(!value.eq_unordered(&Value::Array(vec![
+            nested.clone(),
+            a.clone(),
+            b.clone(),
+            b.clone()
+        ])));
+        assert!(!value.eq_unordered(&Value::Array(vec![a.clone(), b.clone(), nested])));
+        assert!(!value.eq_unordered(&Value::Array(vec![
+            Value::Array(vec![Value::Integer(2), Value::Integer(1)]),
+            a.clone(),
+            a.clone(),
+            b.clone()
+        ])));
+        assert!(Value::Integer(1).eq_unordered(&Value::Integer(1)));
+        assert!(!a.eq_unordered(&b));
+    }
+
+    #[test]
+    fn value_implement_from_option() {
+        let some: Value = Some(5).into();
+        let none: Value = None::<i64>.into();
+
+        assert_eq!(some, Value::Integer(5));
+        assert_eq!(none, Value::Nil);
+        assert_eq!(
+            Value::from(Some(String::from("OK"))),
+            Value::String("OK".into())
+        );
+        assert_eq!(Value::from(Some(Some(5))), Value::Integer(5));
+        assert_eq!(Value::from(Some(None::<Value>)), Value::Nil);
+        assert_eq!(
+            Value::from(vec![Value::from(Some(1)), Value::from(None::<i64>)]),
+            Value::try_from("*2\r\n:1\r\n$-1\r\n").unwrap()
+        );
+    }
+
+    #[test]
+    fn value_normalize() {
+        let mut string = String::with_capacity(64);
+        let mut entries = Vec::with_capacity(64);
+        let mut pairs = Vec::with_capacity(64);
+
+        string.push_str("OK");
+        pairs.push((
+            Value::SimpleString(string.clone()),
+            Value::Error(string.clone()),
+        ));
+        entries.push(Value::String(string));
+        entries.push(Value::Map(pairs));
+
+        let mut value = Value::Array(entries);
+        let expected = value.clone();
+
+        value.normalize();
+        assert_eq!(value, expected);
+
+        let Value::Array(entries) = &value else {
+            unreachable!()
+        };
+        let (Value::String(string), Value::Map(pairs)) = (&entries[0], &entries[1]) else {
+            unreachable!()
+        };
+        let (Value::SimpleString(key), Value::Error(message)) = &pairs[0] else {
+            unreachable!()
+        };
+
+        assert_eq!(entries.capacity(), 2);
+        assert_eq!(pairs.capacity(), 1);
+        assert_eq!(string.capacity(), 2);
+        assert_eq!(key.capacity(), 2);
+        assert_eq!(message.capacity(), 2);
+    }
+
+    #[test]
+    fn value_as_hello() {
+        let source = [
+            "%8\r\n",
+            "$6\r\nserver\r\n$5\r\nredis\r\n",
+            "$7\r\nversion\r\n$5\r\n7.2.4\r\n",
+            "$5\r\nproto\r\n:3\r\n",
+            "$2\r\nid\r\n:42\r\n",
+            "$4\r\nmode\r\n$10\r\nstandalone\r\n",
+            "$4\r\nrole\r\n$6\r\nmaster\r\n",
+            "$7\r\nmodules\r\n*1\r\n%1\r\n$4\r\nname\r\n$4\r\njson\r\n",
+            "$7\r\nunknown\r\n:0\r\n",
+        ]
+        .concat();
+        let hello = HelloInfo {
+            server: Some("redis".into()),
+            version: Some("7.2.4".into()),
+            proto: Some(3),
+            id: Some(42),
+            mode: Some("standalone".into()),
+            role: Some("master".into()),
+            modules: vec![Value::Map(vec![(
+                Value::String("name".into()),
+                Value::String("json".into()),
+            )])],
+        };
+
+        assert_eq!(
+            Value::try_from(source.as_str()).unwrap().as_hello(),
+            Some(hello)
+        );
+        assert_eq!(
+            Value::command(&["server", "redis", "proto", "2"]).as_hello(),
+            Some(HelloInfo {
+                server: Some("redis".into()),
+                ..HelloInfo::default()
+            })
+        );
+        assert_eq!(Value::Map(vec![]).as_hello(), Some(HelloInfo::default()));
+        assert_eq!(Value::command(&["server"]).as_hello(), None);
+        assert_eq!(Value::SimpleString("OK".into()).as_hello(), None);
+    }
+
+    #[test]
+    fn value_retain_array() {
+        let mut value = Value::Array(vec![
+            Value::Nil,
+            Value::Integer(1),
+            Value::Nil,
+            Value::Array(vec![Value::Nil]),
+            Value::Nil,
+        ]);
+
+        value.retain_array(|value| !value.is_nil());
+        assert_eq!(
+            value,
+            Value::Array(vec![Value::Integer(1), Value::Array(vec![Value::Nil])])
+        );
+
+        value.retain_array(|_| false);
+        assert_eq!(value, Value::Array(vec![]));
+
+        let mut value = Value::Integer(1);
+
+        value.retain_array(|_| false);
+        assert_eq!(value, Value::Integer(1));
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_empty_integer() {
+        for (source, error) in [
+            (":\r\n", Error::of_type(INTEGER, 1)),
+            (":-\r\n", Error::of_type(INTEGER, 1)),
+            (":+\r\n", Error::of_type(INTEGER, 1)),
+            ("*1\r\n:\r\n", Error::of_type(INTEGER, 5)),
+            ("*\r\n", Error::of_type(SIZE, 1)),
+            ("$\r\n\r\n", Error::of_type(SIZE, 1)),
+            (":", Error::of_incomplete(INTEGER, 1)),
+            (":\r", Error::of_incomplete(INTEGER, 2)),
+        ] {
+            assert_eq!(Value::try_from(source), Err(error), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn value_estimate_memory() {
+        let size = size_of::<Value>();
+        let mut values = Vec::with_capacity(4);
+
+        values.push(Value::String(String::with_capacity(16)));
+        values.push(Value::Map(vec![(
+            Value::Integer(1),
+            Value::Error("ERR".into()),
+        )]));
+
+        assert_eq!(Value::Nil.estimate_memory(), size);
+        assert_eq!(Value::Integer(1).estimate_memory(), size);
+        assert_eq!(
+            Value::Array(values).estimate_memory(),
+            size + 4 * size + 16 + 2 * size + 3
+        );
+    }
+
+    #[test]
+    fn value_implement_from_array() {
+        let value: Value = [Value::Nil, Value::String("OK".into())].into();
+
+        assert_eq!(
+            value,
+            Value::Array(vec![Value::Nil, Value::String("OK".into())])
+        );
+        assert_eq!(Value::from([] as [Value; 0]), Value::Array(vec![]));
+        assert_eq!(
+            Value::from([Value::from([Value::Integer(1)])]),
+            Value::try_from("*1\r\n*1\r\n:1\r\n").unwrap()
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_bulk_string_size_index() {
+        for (source, error) in [
+            ("$2\r\nHello\r\n", Error::of_size(BULK_STRING, 6)),
+            ("$3\r\nOops\r\n", Error::of_size(BULK_STRING, 7)),
+            ("$4\r\nOopsX\r\n", Error::of_size(BULK_STRING, 8)),
+            ("$4\r\nOops\rX", Error::of_size(BULK_STRING, 8)),
+            ("$5\r\nOops\r\n", Error::of_size(BULK_STRING, 9)),
+            ("$0\r\nOops\r\n", Error::of_size(BULK_STRING, 4)),
+            (
+                "*2\r\n:1\r\n$1\r\nOops\r\n",
+                Error::of_size(BULK_STRING, 13),
+            ),
+            ("$6\r\nOops\r\n", Error::of_incomplete(BULK_STRING, 10)),
+            ("$4\r\nOops\r", Error::of_incomplete(BULK_STRING, 9)),
+            ("$4\r\nOops", Error::of_incomplete(BULK_STRING, 8)),
+        ] {
+            assert_eq!(Value::try_from(source), Err(error), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn value_first_and_last() {
+        let value = Value::try_from("*3\r\n:1\r\n:2\r\n:3\r\n").unwrap();
+        let single = Value::Array(vec![Value::Nil]);
+
+        assert_eq!(value.first(), Some(&Value::Integer(1)));
+        assert_eq!(value.last(), Some(&Value::Integer(3)));
+        assert_eq!(single.first(), Some(&Value::Nil));
+        assert_eq!(single.last(), Some(&Value::Nil));
+        assert_eq!(Value::Array(vec![]).first(), None);
+        assert_eq!(Value::Array(vec![]).last(), None);
+        assert_eq!(Value::String("OK".into()).first(), None);
+        assert_eq!(Value::Map(vec![(Value::Nil, Value::Nil)]).last(), None);
+    }
+
+    #[test]
+    fn value_try_into_pair() {
+        let pair = Value::Array(vec![Value::String("a".into()), Value::Nil]);
+        let triple = Value::Array(vec![Value::Nil, Value::Nil, Value::Nil]);
+
+        assert_eq!(
+            pair.try_into_pair(),
+            Ok((Value::String("a".into()), Value::Nil))
+        );
+        assert_eq!(triple.clone().try_into_pair(), Err(triple));
+        assert_eq!(
+            Value::Array(vec![Value::Nil]).try_into_pair(),
+            Err(Value::Array(vec![Value::Nil]))
+        );
+        assert_eq!(
+            Value::Map(vec![(Value::Nil, Value::Nil)]).try_into_pair(),
+            Err(Value::Map(vec![(Value::Nil, Value::Nil)]))
+        );
+        assert_eq!(Value::Integer(2).try_into_pair(), Err(Value::Integer(2)));
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_empty_bulk_string() {
+        assert_eq!(
+            Value::parse_prefix("$0\r\n\r\n"),
+            Ok((Value::String("".into()), 6))
+        );
+        assert_eq!(
+            Value::try_from("*2\r\n$0\r\n\r\n$0\r\n\r\n"),
+            Ok(Value::Array(vec![
+                Value::String("".into()),
+                Value::String("".into())
+            ]))
+        );
+        assert_eq!(
+            Value::try_from("$0\r\nX\r\n"),
+            Err(Error::of_size(BULK_STRING, 4))
+        );
+        assert_eq!(
+            Value::try_from("$0\r\n\rX"),
+            Err(Error::of_size(BULK_STRING, 4))
+        );
+        assert_eq!(
+            Value::try_from("$0\r\n\n"),
+            Err(Error::of_size(BULK_STRING, 4))
+        );
+        assert_eq!(
+            Value::try_from("$0\r\n"),
+            Err(Error::of_incomplete(BULK_STRING, 4))
+        );
+        assert_eq!(
+            Value::try_from("$0\r\n\r"),
+            Err(Error::of_incomplete(BULK_STRING, 5))
+        );
+    }
+
+    #[test]
+    fn value_contains() {
+        let value = Value::try_from("*3\r\n:1\r\n$-1\r\n*1\r\n:2\r\n").unwrap();
+
+        assert!(value.contains(&Value::Integer(1)));
+        assert!(value.contains(&Value::Nil));
+        assert!(value.contains(&Value::Array(vec![Value::Integer(2)])));
+        assert!(!value.contains(&Value::Integer(2)));
+        assert!(!value.contains(&Value::String("1".into())));
+        assert!(!Value::Array(vec![]).contains(&Value::Nil));
+        assert!(!Value::Integer(1).contains(&Value::Integer(1)));
+        assert!(!Value::Map(vec![(Value::Nil, Value::Nil)]).contains(&Value::Nil));
+    }
+
+    #[test]
+    fn value_frame_len() {
+        for (source, len) in [
+            (":1\r\n", Some(4)),
+            ("$5\r\nHello\r\n+OK\r\n", Some(11)),
+            ("*2\r\n*1\r\n$1\r\nA\r\n%1\r\n:1\r\n$-1\r\n", Some(28)),
+            ("$?\r\n;2\r\nOK\r\n;0\r\n:1\r\n", Some(16)),
+            ("", None),
+            ("$5\r\nHel", None),
+            ("*2\r\n*1\r\n$1\r\nA\r\n", None),
+            ("*?\r\n:1\r\n", None),
+        ] {
+            assert_eq!(Value::frame_len(source), Ok(len), "{source:?}");
+        }
+
+        assert_eq!(
+            Value::frame_len("*2\r\n$1\r\nAB\r\n"),
+            Err(Error::of_size(BULK_STRING, 9))
+        );
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_plus_signed_size() {
+        for (source, error) in [
+            ("$+4\r\nOops\r\n", Error::of_type(SIZE, 1)),
+            ("*+2\r\n:1\r\n:2\r\n", Error::of_type(SIZE, 1)),
+            ("%+1\r\n:1\r\n:2\r\n", Error::of_type(SIZE, 1)),
+            ("*1\r\n$+0\r\n\r\n", Error::of_type(SIZE, 5)),
+            ("$?\r\n;+2\r\nOK\r\n;0\r\n", Error::of_type(SIZE, 5)),
+        ] {
+            assert_eq!(Value::try_from(source), Err(error), "{source:?}");
+        }
+
+        assert_eq!(Value::try_from(":+4\r\n"), Ok(Value::Integer(4)));
+        assert_eq!(
+            Value::try_from("*2\r\n:+1\r\n:-2\r\n"),
+            Ok(Value::Array(vec![Value::Integer(1), Value::Integer(-2)]))
+        );
+    }
+
+    #[test]
+    fn value_sort_array() {
+        let mut value = Value::try_from("*4\r\n:3\r\n:-1\r\n:2\r\n:0\r\n").unwrap();
+
+        value.sort_array();
+        assert_eq!(
+            value,
+            Value::try_from("*4\r\n:-1\r\n:0\r\n:2\r\n:3\r\n").unwrap()
+        );
+
+        value.sort_array_by(|a, b| b.cmp(a));
+        assert_eq!(
+            value,
+            Value::try_from("*4\r\n:3\r\n:2\r\n:0\r\n:-1\r\n").unwrap()
+        );
+
+        let mut value = Value::String("ba".into());
+
+        value.sort_array();
+        assert_eq!(value, Value::String("ba".into()));
+    }
+
+    #[test]
+    fn value_implement_try_from_resp_integer_grammar() {
+        for source in [
+            ":0x10\r\n",
+            ":1_000\r\n",
+            ":1e3\r\n",
+            ":1.0\r\n",
+            ":0b1\r\n",
+            ":--1\r\n",
+            ":+-1\r\n",
+            ":1-\r\n",
+            ": 1\r\n",
+            ":1 \r\n",
+            ":\u{661}\r\n",
+            ":\u{ff11}\r\n",
+        ] {
+            assert_eq!(
+                Value::try_from(source),
+                Err(Error::of_type(INTEGER, 1)),
+                "{source:?}"
+            );
+        }
+
+        for (source, integer) in [
+            (":0\r\n", 0),
+            (":-0\r\n", 0),
+            (":+0\r\n", 0),
+            (":007\r\n", 7),
+            (":-007\r\n", -7),
+            (":9223372036854775807\r\n", i64::MAX),
+            (":-9223372036854775808\r\n", i64::MIN),
+        ] {
+            assert_eq!(
+                Value::try_from(source),
+                Ok(Value::Integer(integer)),
+                "{source:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn value_as_f64() {
+        assert_eq!(Value::Integer(0).as_f64(), Some(0.0));
+        assert_eq!(Value::Integer(-42).as_f64(), Some(-42.0));
+        assert_eq!(Value::Integer(1 << 53).as_f64(), Some(9007199254740992.0));
+        assert_eq!(
+            Value::Integer((1 << 53) + 1).as_f64(),
+            Some(9007199254740992.0)
+        );
+        assert_eq!(
+            Value::Integer(i64::MIN).as_f64(),
+            Some(-9223372036854775808.0)
+        );
+        assert_eq!(Value::Nil.as_f64(), None);
+        assert_eq!(Value::SimpleString("1.5".into()).as_f64(), None);
+    }
+
+    #[test]
+    fn value_encode_nil_per_protocol() {
+        let resp3 = EncodeOptions { resp3: true };
+        let value = Value::Map(vec![(
+            Value::Nil,
+            Value::Array(vec![Value::Integer(1), Value::Nil]),
+        )]);
+
+        assert_eq!(Value::Nil.to_resp_bytes(), b"$-1\r\n");
+        assert_eq!(Value::Nil.to_string(), "$-1\r\n");
+        assert_eq!(
+            Value::Nil.to_resp_bytes_with(&EncodeOptions::default()),
+            b"$-1\r\n"
+        );
+        assert_eq!(Value::Nil.to_resp_bytes_with(&resp3), b"_\r\n");
+        assert_eq!(value.to_resp_bytes(), b"%1\r\n$-1\r\n*2\r\n:1\r\n$-1\r\n");
+        assert_eq!(
+            value.to_resp_bytes_with(&resp3),
+            b"%1\r\n_\r\n*2\r\n:1\r\n_\r\n"
         );
+        assert_eq!(value.encoded_len(), value.to_resp_bytes().len());
+        assert_eq!(
+            value.encoded_len_with(&resp3),
+            value.to_resp_bytes_with(&resp3).len()
+        );
+    }
+
+    #[test]
+    fn value_parse_resp3_null() {
+        let resp3 = EncodeOptions { resp3: true };
+        let value = Value::Map(vec![(
+            Value::Nil,
+            Value::Array(vec![Value::Integer(1), Value::Nil]),
+        )]);
+
+        for value in [Value::Nil, value] {
+            let encoded = value.to_resp_bytes_with(&resp3);
+
+            assert_eq!(Value::try_from(&encoded[..]), Ok(value));
+        }
+        assert_eq!(
+            Value::parse_with(
+                "_\n",
+                &ParseOptions {
+                    bare_lf: true,
+                    ..Default::default()
+                }
+            ),
+            Ok(Value::Nil)
+        );
+        assert_eq!(Value::parse_prefix("_\r\n:1\r\n"), Ok((Value::Nil, 3)));
+    }
+
+    #[test]
+    fn value_parse_resp3_null_report_errors() {
+        for (source, error) in [
+            ("_", Error::of_incomplete(NIL, 1)),
+            ("_\r", Error::of_incomplete(NIL, 2)),
+            ("*2\r\n_\r\n", Error::of_incomplete(ARRAY, 7)),
+            ("_\n", Error::of_unexpected(NIL, 1, b'\n')),
+            ("_\rX", Error::of_unexpected(NIL, 2, b'X')),
+            ("_0\r\n", Error::of_unexpected(NIL, 1, b'0')),
+            ("*1\r\n_ \r\n", Error::of_unexpected(NIL, 5, b' ')),
+        ] {
+            assert_eq!(Value::try_from(source), Err(error), "{source:?}");
+        }
     }
 }
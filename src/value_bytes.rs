@@ -0,0 +1,324 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::Utf8Error;
+
+use bytes::Bytes;
+
+use crate::error::Error as TError;
+use crate::parser;
+use crate::value::Value;
+use crate::value_ref::ValueRef;
+
+/// A RESP value sharing its strings with the [`Bytes`] buffer it is parsed
+/// from.
+///
+/// This is the counterpart of [`ValueRef`] for network servers built on the
+/// `bytes` crate: its strings are [`Bytes`] slices of that buffer, which
+/// share its allocation rather than copying it, and don't borrow from it.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytes::Bytes;
+/// use squall_dot_io_resp::{Value, ValueBytes};
+///
+/// let source = Bytes::from_static(b"*2\r\n+Hourly\r\n:447\r\n");
+/// let value = ValueBytes::parse(&source).unwrap();
+///
+/// assert_eq!(
+///     value,
+///     ValueBytes::Array(vec![
+///         ValueBytes::SimpleString(source.slice(5..11)),
+///         ValueBytes::Integer(447)
+///     ])
+/// );
+/// assert_eq!(
+///     value.to_owned(),
+///     Ok(Value::Array(vec![Value::SimpleString("Hourly".into()), Value::Integer(447)]))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValueBytes {
+    /// Denote the absence of value.
+    Nil,
+    /// Denote and integer value, wrapped as singleton tuple.
+    Integer(i64),
+    /// Denote an error, wrapped as descriptive message slice.
+    Error(Bytes),
+    /// Denote a bulk string value, wrapped as singleton tuple.
+    String(Bytes),
+    /// Denote a simple string value, wrapped as singleton tuple.
+    SimpleString(Bytes),
+    /// Denote a RESP3 streamed string value, wrapped as the vector of its
+    /// non-empty chunks, as [`ValueRef::StreamedString`] is.
+    StreamedString(Vec<Bytes>),
+    /// Denote a non-nil list of values, wrapped as singleton vector of
+    /// ValueBytes.
+    Array(Vec<ValueBytes>),
+    /// Denote a RESP3 map, wrapped as vector of key/value pairs.
+    Map(Vec<(ValueBytes, ValueBytes)>),
+}
+
+impl ValueBytes {
+    /// Parse the RESP value found at the start of `source`, sharing its
+    /// buffer.
+    ///
+    /// As with [`Value`]'s `TryFrom<&[u8]>`, trailing bytes are ignored, and
+    /// `source` must be valid UTF-8 up to the end of the value.
+    pub fn parse(source: &Bytes) -> Result<ValueBytes, TError> {
+        ValueBytes::parse_prefix(source).map(|(value, _)| value)
+    }
+
+    /// Parse the RESP value found at the start of `source`, sharing its
+    /// buffer, along with the number of bytes it spans, as
+    /// [`Value::parse_prefix`] does.
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use squall_dot_io_resp::ValueBytes;
+    ///
+    /// let mut source = Bytes::from_static(b"$2\r\nOK\r\n:1\r\n");
+    /// let (value, size) = ValueBytes::parse_prefix(&source).unwrap();
+    ///
+    /// assert_eq!(value, ValueBytes::String(Bytes::from_static(b"OK")));
+    /// assert_eq!(source.split_off(size), Bytes::from_static(b":1\r\n"));
+    /// ```
+    pub fn parse_prefix(source: &Bytes) -> Result<(ValueBytes, usize), TError> {
+        let (value, size) = parser::parse_utf8(source, ValueRef::parse_prefix)?;
+
+        Ok((ValueBytes::share(source, value), size))
+    }
+
+    /// Copy the strings into an owned [`Value`], concatenating the chunks of
+    /// streamed strings.
+    ///
+    /// This fails on strings which aren't valid UTF-8: parsed values never
+    /// hold such strings, but values built by hand may.
+    pub fn to_owned(&self) -> Result<Value, Utf8Error> {
+        let string = |bytes: &Bytes| core::str::from_utf8(bytes).map(String::from);
+
+        Ok(match self {
+            ValueBytes::Nil => Value::Nil,
+            ValueBytes::Integer(integer) => Value::Integer(*integer),
+            ValueBytes::Error(message) => Value::Error(string(message)?),
+            ValueBytes::String(bytes) => Value::String(string(bytes)?),
+            ValueBytes::SimpleString(bytes) => Value::SimpleString(string(bytes)?),
+            ValueBytes::StreamedString(chunks) => Value::String(
+                String::from_utf8(chunks.concat()).map_err(|error| error.utf8_error())?,
+            ),
+            ValueBytes::Array(values) => Value::Array(
+                values
+                    .iter()
+                    .map(ValueBytes::to_owned)
+                    .collect::<Result<_, _>>()?,
+            ),
+            ValueBytes::Map(pairs) => Value::Map(
+                pairs
+                    .iter()
+                    .map(|(key, value)| Ok((key.to_owned()?, value.to_owned()?)))
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+
+    /// Encode as a RESP string, for logging and debugging purposes only.
+    ///
+    /// This is lossy: invalid UTF-8 sequences in strings are replaced by
+    /// [`char::REPLACEMENT_CHARACTER`], while bulk string lengths remain
+    /// those of the original bytes. Hence, the result isn't meant to be sent
+    /// over the wire, nor to be parsed back. Streamed strings are encoded as
+    /// such, chunk by chunk.
+    ///
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use squall_dot_io_resp::ValueBytes;
+    ///
+    /// assert_eq!(
+    ///     ValueBytes::String(Bytes::from_static(b"O\xffK")).to_resp_string_lossy(),
+    ///     "$3\r\nO\u{FFFD}K\r\n"
+    /// );
+    /// ```
+    pub fn to_resp_string_lossy(&self) -> String {
+        let mut buffer = Vec::new();
+
+        self.write_resp(&mut buffer);
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+
+    fn write_resp(&self, buffer: &mut Vec<u8>) {
+        let line = |buffer: &mut Vec<u8>, kind: u8, bytes: &[u8]| {
+            buffer.push(kind);
+            buffer.extend_from_slice(bytes);
+            buffer.extend_from_slice(b"\r\n");
+        };
+        let bulk = |buffer: &mut Vec<u8>, kind: u8, bytes: &[u8]| {
+            line(buffer, kind, format!("{}", bytes.len()).as_bytes());
+            buffer.extend_from_slice(bytes);
+            buffer.extend_from_slice(b"\r\n");
+        };
+
+        match self {
+            ValueBytes::Nil => buffer.extend_from_slice(b"$-1\r\n"),
+            ValueBytes::Integer(integer) => line(buffer, b':', format!("{integer}").as_bytes()),
+            ValueBytes::Error(message) => line(buffer, b'-', message),
+            ValueBytes::String(bytes) => bulk(buffer, b'$', bytes),
+            ValueBytes::SimpleString(bytes) => line(buffer, b'+', bytes),
+            ValueBytes::StreamedString(chunks) => {
+                buffer.extend_from_slice(b"$?\r\n");
+                chunks.iter().for_each(|chunk| bulk(buffer, b';', chunk));
+                buffer.extend_from_slice(b";0\r\n");
+            }
+            ValueBytes::Array(values) => {
+                line(buffer, b'*', format!("{}", values.len()).as_bytes());
+                values.iter().for_each(|value| value.write_resp(buffer));
+            }
+            ValueBytes::Map(pairs) => {
+                line(buffer, b'%', format!("{}", pairs.len()).as_bytes());
+                pairs.iter().for_each(|(key, value)| {
+                    key.write_resp(buffer);
+                    value.write_resp(buffer);
+                });
+            }
+        }
+    }
+
+    /// Turn the strings `value` borrows from `source` into slices of it.
+    fn share(source: &Bytes, value: ValueRef) -> ValueBytes {
+        let slice = |string: &str| source.slice_ref(string.as_bytes());
+
+        match value {
+            ValueRef::Nil => ValueBytes::Nil,
+            ValueRef::Integer(integer) => ValueBytes::Integer(integer),
+            ValueRef::Error(message) => ValueBytes::Error(slice(message)),
+            ValueRef::String(string) => ValueBytes::String(slice(string)),
+            ValueRef::SimpleString(string) => ValueBytes::SimpleString(slice(string)),
+            ValueRef::StreamedString(chunks) => {
+                ValueBytes::StreamedString(chunks.into_iter().map(slice).collect())
+            }
+            ValueRef::Array(values) => ValueBytes::Array(
+                values
+                    .into_iter()
+                    .map(|value| ValueBytes::share(source, value))
+                    .collect(),
+            ),
+            ValueRef::Map(pairs) => ValueBytes::Map(
+                pairs
+                    .into_iter()
+                    .map(|(key, value)| {
+                        (
+                            ValueBytes::share(source, key),
+                            ValueBytes::share(source, value),
+                        )
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::Node::{BULK_STRING, SIMPLE_STRING};
+
+    use super::super::{Error, Value, ValueBytes};
+
+    #[test]
+    fn value_bytes_share_source_allocation() {
+        let source = Bytes::from(b"*2\r\n$7\r\nOh\r\nOh!\r\n%1\r\n-Oh oh!\r\n+Hourly\r\n".to_vec());
+        let value = ValueBytes::parse(&source).unwrap();
+        let within = |bytes: &Bytes| source.as_ptr_range().contains(&bytes.as_ptr());
+
+        match &value {
+            ValueBytes::Array(values) => match &values[..] {
+                [ValueBytes::String(string), ValueBytes::Map(pairs)] => {
+                    assert_eq!(string, &b"Oh\r\nOh!"[..]);
+                    assert_eq!(string.as_ptr(), source[8..].as_ptr());
+                    assert!(matches!(
+                        &pairs[..],
+                        [(ValueBytes::Error(message), ValueBytes::SimpleString(string))]
+                            if within(message) && within(string)
+                    ));
+                }
+                r#else => panic!("Unexpected {:?}", r#else),
+            },
+            r#else => panic!("Unexpected {:?}", r#else),
+        }
+
+        drop(source);
+        assert_eq!(
+            value.to_owned(),
+            Ok(Value::Array(vec![
+                Value::String("Oh\r\nOh!".into()),
+                Value::Map(vec![(
+                    Value::Error("Oh oh!".into()),
+                    Value::SimpleString("Hourly".into())
+                )]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn value_bytes_share_streamed_string_chunks() {
+        let source = Bytes::from_static(b"$?\r\n;4\r\nHell\r\n;1\r\no\r\n;0\r\n");
+        let value = ValueBytes::parse(&source).unwrap();
+
+        match &value {
+            ValueBytes::StreamedString(chunks) => {
+                assert_eq!(chunks, &[&b"Hell"[..], &b"o"[..]]);
+                assert_eq!(chunks[0].as_ptr(), source[8..].as_ptr());
+                assert_eq!(chunks[1].as_ptr(), source[18..].as_ptr());
+            }
+            r#else => panic!("Unexpected {:?}", r#else),
+        }
+        assert_eq!(value.to_owned(), Ok(Value::String("Hello".into())));
+    }
+
+    #[test]
+    fn value_bytes_report_errors() {
+        assert_eq!(
+            ValueBytes::parse(&Bytes::from_static(b"$5\r\nOops\r\n")),
+            Err(Error::of_size(BULK_STRING, 9))
+        );
+        assert_eq!(
+            ValueBytes::parse(&Bytes::from_static(b"+O\xffK\r\n")),
+            Err(Error::of_unexpected(SIMPLE_STRING, 2, 0xff))
+        );
+        assert_eq!(
+            ValueBytes::parse(&Bytes::from_static(b"+OK\r\n\xff")),
+            Ok(ValueBytes::SimpleString(Bytes::from_static(b"OK")))
+        );
+    }
+
+    #[test]
+    fn value_bytes_to_owned_invalid_utf8() {
+        let value = ValueBytes::Array(vec![ValueBytes::String(Bytes::from_static(b"O\xffK"))]);
+
+        assert!(value.to_owned().is_err());
+    }
+
+    #[test]
+    fn value_bytes_to_resp_string_lossy() {
+        let value = ValueBytes::Array(vec![
+            ValueBytes::String(Bytes::from_static(b"Oh\xff\xfe!")),
+            ValueBytes::Map(vec![(
+                ValueBytes::Error(Bytes::from_static(b"\xc3")),
+                ValueBytes::Nil,
+            )]),
+            ValueBytes::StreamedString(vec![Bytes::from_static(b"Oh"), Bytes::from_static(b"!")]),
+        ]);
+
+        assert_eq!(
+            value.to_resp_string_lossy(),
+            "*3\r\n$5\r\nOh\u{FFFD}\u{FFFD}!\r\n%1\r\n-\u{FFFD}\r\n$-1\r\n$?\r\n;2\r\nOh\r\n;1\r\n!\r\n;0\r\n"
+        );
+        assert_eq!(
+            ValueBytes::parse(&Bytes::from_static(b"*2\r\n+OK\r\n:7\r\n"))
+                .unwrap()
+                .to_resp_string_lossy(),
+            "*2\r\n+OK\r\n:7\r\n"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::error::Error as TError;
+use crate::parser;
+use crate::value::{pairs, Value};
+use crate::visitor::Visitor;
+use crate::Node::UNKNOWN;
+
+/// A RESP value borrowing its strings from the parsed RESP string.
+///
+/// This is the zero-copy counterpart of [`Value`]: strings and errors are
+/// never copied, so that parsing a lone one allocates nothing at all. Parsing
+/// aggregates still allocates, though:
+///
+/// + the [`Vec`]s of [`ValueRef::Array`] entries and of [`ValueRef::Map`]
+///   pairs, the latter being first read as a flat [`Vec`] of keys and values,
+/// + the [`Vec`] of [`ValueRef::StreamedString`] chunks,
+/// + and the stacks of the aggregates being read, in the parser and in the
+///   builder of the value, which grow with the nesting depth. Attributes are
+///   read into such a [`Vec`] as well, before being dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use squall_dot_io_resp::{Value, ValueRef};
+///
+/// let source = "*2\r\n+Hourly\r\n:447\r\n";
+/// let value = ValueRef::parse(source).unwrap();
+///
+/// assert_eq!(
+///     value,
+///     ValueRef::Array(vec![ValueRef::SimpleString("Hourly"), ValueRef::Integer(447)])
+/// );
+/// assert_eq!(
+///     value.to_owned(),
+///     Value::Array(vec![Value::SimpleString("Hourly".into()), Value::Integer(447)])
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValueRef<'a> {
+    /// Denote the absence of value.
+    Nil,
+    /// Denote and integer value, wrapped as singleton tuple.
+    Integer(i64),
+    /// Denote an error, wrapped as descriptive message slice.
+    Error(&'a str),
+    /// Denote a bulk string value, wrapped as singleton tuple.
+    String(&'a str),
+    /// Denote a simple string value, wrapped as singleton tuple.
+    SimpleString(&'a str),
+    /// Denote a RESP3 streamed string value, wrapped as the vector of its
+    /// non-empty chunks.
+    ///
+    /// Unlike [`Value`], which concatenates them into a [`Value::String`],
+    /// the chunks are kept apart, as they can't be borrowed at once.
+    StreamedString(Vec<&'a str>),
+    /// Denote a non-nil list of values, wrapped as singleton vector of
+    /// ValueRef.
+    Array(Vec<ValueRef<'a>>),
+    /// Denote a RESP3 map, wrapped as vector of key/value pairs.
+    Map(Vec<(ValueRef<'a>, ValueRef<'a>)>),
+}
+
+/// Build a [`ValueRef`] tree out of the parsed tokens.
+#[derive(Debug, Default)]
+struct Builder<'a> {
+    /// Arrays being filled, innermost last.
+    arrays: Vec<Vec<ValueRef<'a>>>,
+    /// Chunks of the streamed string being read.
+    chunks: Vec<&'a str>,
+    /// The top-level value, once complete.
+    value: Option<ValueRef<'a>>,
+}
+
+impl<'a> Builder<'a> {
+    fn push(&mut self, value: ValueRef<'a>) {
+        match self.arrays.last_mut() {
+            Some(values) => values.push(value),
+            None => self.value = Some(value),
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for Builder<'a> {
+    fn on_nil(&mut self) {
+        self.push(ValueRef::Nil)
+    }
+
+    fn on_integer(&mut self, integer: i64) {
+        self.push(ValueRef::Integer(integer))
+    }
+
+    fn on_error(&mut self, message: &'a str) {
+        self.push(ValueRef::Error(message))
+    }
+
+    fn on_string(&mut self, string: &'a str) {
+        self.push(ValueRef::String(string))
+    }
+
+    fn on_simple_string(&mut self, string: &'a str) {
+        self.push(ValueRef::SimpleString(string))
+    }
+
+    fn on_string_chunk(&mut self, chunk: &'a str) {
+        self.chunks.push(chunk)
+    }
+
+    fn on_streamed_string_end(&mut self) {
+        let chunks = core::mem::take(&mut self.chunks);
+        self.push(ValueRef::StreamedString(chunks))
+    }
+
+    fn on_array_start(&mut self, _len: usize) {
+        self.arrays.push(vec![])
+    }
+
+    fn on_array_end(&mut self) {
+        let values = self.arrays.pop().unwrap_or_default();
+        self.push(ValueRef::Array(values))
+    }
+
+    fn on_map_start(&mut self, _len: usize) {
+        self.arrays.push(vec![])
+    }
+
+    fn on_map_end(&mut self) {
+        let values = self.arrays.pop().unwrap_or_default();
+        self.push(ValueRef::Map(pairs(values)))
+    }
+
+    fn on_attribute_start(&mut self, _len: usize) {
+        self.arrays.push(vec![])
+    }
+
+    /// Attributes are dropped, as they are by [`Value`]'s `TryFrom<&str>`.
+    fn on_attribute_end(&mut self) {
+        self.arrays.pop();
+    }
+}
+
+impl<'a> ValueRef<'a> {
+    /// Parse the RESP value found at the start of `source`, borrowing from it.
+    ///
+    /// As with [`Value`]'s `TryFrom<&str>`, trailing bytes are ignored.
+    pub fn parse(source: &'a str) -> Result<ValueRef<'a>, TError> {
+        ValueRef::parse_prefix(source).map(|(value, _)| value)
+    }
+
+    /// Copy the borrowed strings into an owned [`Value`].
+    pub fn to_owned(&self) -> Value {
+        match self {
+            ValueRef::Nil => Value::Nil,
+            ValueRef::Integer(integer) => Value::Integer(*integer),
+            ValueRef::Error(message) => Value::Error(message.to_string()),
+            ValueRef::String(string) => Value::String(string.to_string()),
+            ValueRef::SimpleString(string) => Value::SimpleString(string.to_string()),
+            ValueRef::StreamedString(chunks) => Value::String(chunks.concat()),
+            ValueRef::Array(values) => {
+                Value::Array(values.iter().map(ValueRef::to_owned).collect())
+            }
+            ValueRef::Map(pairs) => Value::Map(
+                pairs
+                    .iter()
+                    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub(crate) fn parse_prefix(source: &'a str) -> Result<(ValueRef<'a>, usize), TError> {
+        let mut builder = Builder::default();
+        let size = parser::visit(source, &mut builder)?;
+
+        match builder.value {
+            Some(value) => Ok((value, size)),
+            None => Err(TError::of_incomplete(UNKNOWN, size)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Node::{ARRAY, BULK_STRING};
+
+    use super::super::{Error, ParseOptions, Value, ValueRef};
+
+    #[test]
+    fn value_ref_borrow_from_source() {
+        let source = "+Anatomy\r\n";
+
+        match ValueRef::parse(source) {
+            Ok(ValueRef::SimpleString(string)) => {
+                assert_eq!(string, "Anatomy");
+                assert_eq!(string.as_ptr(), source[1..].as_ptr());
+            }
+            r#else => panic!("Unexpected {:?}", r#else),
+        }
+    }
+
+    #[test]
+    fn value_ref_borrow_nested_from_source() {
+        let source = "*2\r\n$7\r\nOh\r\nOh!\r\n*1\r\n-Oh oh!\r\n";
+        let value = ValueRef::parse(source).unwrap();
+
+        assert_eq!(
+            value,
+            ValueRef::Array(vec![
+                ValueRef::String("Oh\r\nOh!"),
+                ValueRef::Array(vec![ValueRef::Error("Oh oh!")]),
+            ])
+        );
+        match &value {
+            ValueRef::Array(values) => match &values[..] {
+                [ValueRef::String(string), ValueRef::Array(values)] => {
+                    assert_eq!(string.as_ptr(), source[8..].as_ptr());
+                    assert!(matches!(
+                        values[..],
+                        [ValueRef::Error(message)] if message.as_ptr() == source[22..].as_ptr()
+                    ));
+                }
+                r#else => panic!("Unexpected {:?}", r#else),
+            },
+            r#else => panic!("Unexpected {:?}", r#else),
+        }
+    }
+
+    #[test]
+    fn value_ref_to_owned() {
+        let source = "*4\r\n$-1\r\n:447\r\n+Hourly\r\n%1\r\n+A\r\n*0\r\n";
+
+        assert_eq!(
+            ValueRef::parse(source).unwrap().to_owned(),
+            Value::try_from(source).unwrap()
+        );
+        assert_eq!(
+            ValueRef::parse("$5\r\nOops\r\n"),
+            Err(Error::of_size(BULK_STRING, 9))
+        );
+    }
+
+    #[test]
+    fn value_ref_parse_wide_array() {
+        let len = 100_000;
+        let source = format!("*{len}\r\n{}", ":1\r\n".repeat(len));
+
+        match ValueRef::parse(&source) {
+            Ok(ValueRef::Array(values)) => {
+                assert_eq!(values.len(), len);
+                assert!(values.iter().all(|value| ValueRef::Integer(1) == *value));
+            }
+            r#else => panic!("Unexpected {:?}", r#else),
+        }
+    }
+
+    #[test]
+    fn value_ref_parse_deep_array() {
+        let max = ParseOptions::DEFAULT_MAX_DEPTH;
+        let source = format!("{}+OK\r\n", "*1\r\n".repeat(max));
+        let value = ValueRef::parse(&source).unwrap();
+
+        assert_eq!(value.clone(), value);
+        assert_eq!(value.to_owned(), Value::try_from(source.as_str()).unwrap());
+        drop(value);
+
+        let deepest = "*1\r\n".repeat(1_000_000);
+
+        assert_eq!(
+            ValueRef::parse(&deepest),
+            Err(Error::of_overflow(ARRAY, 4 * max))
+        );
+    }
+
+    #[test]
+    fn value_ref_parse_nested_array() {
+        assert_eq!(
+            ValueRef::parse("*3\r\n*0\r\n*1\r\n*1\r\n:1\r\n+A\r\n"),
+            Ok(ValueRef::Array(vec![
+                ValueRef::Array(vec![]),
+                ValueRef::Array(vec![ValueRef::Array(vec![ValueRef::Integer(1)])]),
+                ValueRef::SimpleString("A"),
+            ]))
+        );
+        assert_eq!(
+            ValueRef::parse("*2\r\n*1\r\n:1\r\n"),
+            Err(Error::of_incomplete(ARRAY, 12))
+        );
+    }
+
+    #[test]
+    fn value_ref_borrow_streamed_string_chunks() {
+        let source = "$?\r\n;4\r\nHell\r\n;1\r\no\r\n;0\r\n";
+        let value = ValueRef::parse(source).unwrap();
+
+        match &value {
+            ValueRef::StreamedString(chunks) => {
+                assert_eq!(chunks, &["Hell", "o"]);
+                assert_eq!(chunks[0].as_ptr(), source[8..].as_ptr());
+                assert_eq!(chunks[1].as_ptr(), source[18..].as_ptr());
+            }
+            r#else => panic!("Unexpected {:?}", r#else),
+        }
+        assert_eq!(value.to_owned(), Value::String("Hello".into()));
+        assert_eq!(
+            ValueRef::parse("$?\r\n;0\r\n"),
+            Ok(ValueRef::StreamedString(vec![]))
+        );
+    }
+
+    #[test]
+    fn value_ref_skip_attributes() {
+        assert_eq!(
+            ValueRef::parse("|1\r\n+ttl\r\n:60\r\n*1\r\n|1\r\n+a\r\n:1\r\n+OK\r\n"),
+            Ok(ValueRef::Array(vec![ValueRef::SimpleString("OK")]))
+        );
+    }
+}
@@ -0,0 +1,95 @@
+/// Callbacks of the SAX-style parsing run by [`super::Value::parse_events`].
+///
+/// Tokens are reported as soon as they are read, in RESP string order, so that
+/// no tree of values is built: arrays are reported as an
+/// [`Visitor::on_array_start`] call, followed by the calls for their entries,
+/// and closed by an [`Visitor::on_array_end`] call. Strings are borrowed from
+/// the parsed RESP string.
+///
+/// Every callback does nothing by default, so that only the relevant ones need
+/// to be implemented.
+///
+/// # Examples
+///
+/// ```rust
+/// use squall_dot_io_resp::{Value, Visitor};
+///
+/// #[derive(Default)]
+/// struct Counter(usize);
+///
+/// impl Visitor<'_> for Counter {
+///     fn on_string(&mut self, _: &str) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let mut counter = Counter::default();
+///
+/// assert_eq!(Value::parse_events("*2\r\n$1\r\nA\r\n+B\r\n", &mut counter), Ok(15));
+/// assert_eq!(counter.0, 2);
+/// ```
+pub trait Visitor<'a> {
+    /// Called on a null bulk string.
+    fn on_nil(&mut self) {}
+
+    /// Called on an integer.
+    fn on_integer(&mut self, _integer: i64) {}
+
+    /// Called on an error, with its message.
+    fn on_error(&mut self, _message: &'a str) {}
+
+    /// Called on a bulk string, and on simple strings unless
+    /// [`Visitor::on_simple_string`] is implemented.
+    fn on_string(&mut self, _string: &'a str) {}
+
+    /// Called on a simple string.
+    fn on_simple_string(&mut self, string: &'a str) {
+        self.on_string(string)
+    }
+
+    /// Called when a RESP3 streamed string (`$?`) starts.
+    ///
+    /// Its chunks are then reported with [`Visitor::on_string_chunk`], rather
+    /// than with [`Visitor::on_string`], and its end with
+    /// [`Visitor::on_streamed_string_end`].
+    fn on_streamed_string_start(&mut self) {}
+
+    /// Called on a non-empty chunk of a RESP3 streamed string.
+    fn on_string_chunk(&mut self, _chunk: &'a str) {}
+
+    /// Called when a RESP3 streamed string ends, after all its chunks.
+    fn on_streamed_string_end(&mut self) {}
+
+    /// Called when an array starts, with its entries count.
+    fn on_array_start(&mut self, _len: usize) {}
+
+    /// Called when a RESP3 streamed array (`*?`) starts, and calls
+    /// [`Visitor::on_array_start`] with a `0` entries count unless
+    /// implemented: its entries are only counted once the `.` terminator is
+    /// read, which is reported with [`Visitor::on_array_end`].
+    fn on_streamed_array_start(&mut self) {
+        self.on_array_start(0)
+    }
+
+    /// Called when an array ends, after all its entries.
+    fn on_array_end(&mut self) {}
+
+    /// Called when a RESP3 map starts, with its key/value pairs count.
+    ///
+    /// Its keys and values are then reported in turn, as array entries are.
+    fn on_map_start(&mut self, _len: usize) {}
+
+    /// Called when a RESP3 map ends, after all its keys and values.
+    fn on_map_end(&mut self) {}
+
+    /// Called when a RESP3 attribute (`|`) starts, with its key/value pairs
+    /// count.
+    ///
+    /// Its keys and values are then reported in turn, as map ones are, so
+    /// that visitors building values should set them apart from the value
+    /// they annotate, which is reported after [`Visitor::on_attribute_end`].
+    fn on_attribute_start(&mut self, _len: usize) {}
+
+    /// Called when a RESP3 attribute ends, after all its keys and values.
+    fn on_attribute_end(&mut self) {}
+}
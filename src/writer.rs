@@ -0,0 +1,112 @@
+use crate::options::EncodeOptions;
+use crate::value::Value;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A buffer serializing several [`Value`]s one after the other, e.g. to send
+/// a pipeline of commands at once.
+///
+/// ```rust
+/// use squall_dot_io_resp::{RespWriter, Value};
+///
+/// let mut writer = RespWriter::new();
+///
+/// writer.push(&Value::command(&["GET", "a"]));
+/// writer.push(&Value::command(&["GET", "b"]));
+///
+/// assert_eq!(
+///     writer.as_bytes(),
+///     b"*2\r\n$3\r\nGET\r\n$1\r\na\r\n*2\r\n$3\r\nGET\r\n$1\r\nb\r\n"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RespWriter {
+    /// The RESP wire form of the values pushed so far.
+    buffer: String,
+}
+
+impl RespWriter {
+    /// Create an empty writer.
+    pub fn new() -> RespWriter {
+        RespWriter::default()
+    }
+
+    /// Append the RESP wire form of `value`, as given by
+    /// [`Value::to_resp_bytes`].
+    ///
+    /// [`Value::Nil`] is always written as the RESP2 null, `$-1\r\n`: use
+    /// [`RespWriter::push_with`] for the RESP3 one.
+    pub fn push(&mut self, value: &Value) -> &mut RespWriter {
+        self.push_with(value, &EncodeOptions::default())
+    }
+
+    /// Append the RESP wire form of `value`, as given by
+    /// [`Value::to_resp_bytes_with`] with the same `options`.
+    ///
+    /// Room for it is reserved upfront, as [`Value::encoded_len_with`] counts
+    /// it.
+    pub fn push_with(&mut self, value: &Value, options: &EncodeOptions) -> &mut RespWriter {
+        self.buffer.reserve(value.encoded_len_with(options));
+
+        // Writing into a `String` can't fail.
+        let _ = value.write_resp(&mut self.buffer, options);
+        self
+    }
+
+    /// Borrow the bytes written so far.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buffer.as_bytes()
+    }
+
+    /// Whether nothing was written yet.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Take the bytes written so far.
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{EncodeOptions, RespWriter, Value};
+
+    #[test]
+    fn resp_writer_build_pipeline() {
+        let set = Value::command(&["SET", "key", "val"]);
+        let get = Value::command(&["GET", "key"]);
+        let mut writer = RespWriter::new();
+
+        assert!(writer.is_empty());
+        writer.push(&set).push(&get);
+        assert!(!writer.is_empty());
+        assert_eq!(
+            writer.as_bytes(),
+            b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$3\r\nval\r\n*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n"
+        );
+        assert_eq!(
+            Value::parse_all(core::str::from_utf8(writer.as_bytes()).unwrap()),
+            Ok((vec![set.clone(), get.clone()], writer.as_bytes().len()))
+        );
+        assert_eq!(
+            writer.finish(),
+            [set.to_resp_bytes(), get.to_resp_bytes()].concat()
+        );
+    }
+
+    #[test]
+    fn resp_writer_push_with_options() {
+        let value = Value::Array(vec![Value::Nil, Value::Integer(1)]);
+        let resp3 = EncodeOptions { resp3: true };
+        let mut writer = RespWriter::new();
+
+        writer.push(&value).push_with(&value, &resp3);
+        assert_eq!(writer.as_bytes(), b"*2\r\n$-1\r\n:1\r\n*2\r\n_\r\n:1\r\n");
+        assert_eq!(
+            writer.finish(),
+            [value.to_resp_bytes(), value.to_resp_bytes_with(&resp3)].concat()
+        );
+    }
+}